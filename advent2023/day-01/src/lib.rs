@@ -0,0 +1,117 @@
+//! Day 1: Trebuchet?!
+
+use std::io::{self, BufRead};
+
+/// First and last digit found in a line, or zero if there are none.
+/// With `use_words`, spelled out digits like "seven" also count.
+pub fn find_numbers(s: &str, use_words: bool) -> (u32, u32) {
+  let mut first: Option<u32> = None; 
+  let mut last: Option<u32> = None;
+  let mut curr: Option<u32>;
+
+  for (i, _) in s.char_indices() {
+    curr = None;
+    let ss = &s[i..];
+    if ss.starts_with('1') || (use_words && ss.starts_with("one")) { curr = Some(1); }
+    if ss.starts_with('2') || (use_words && ss.starts_with("two")) { curr = Some(2); }
+    if ss.starts_with('3') || (use_words && ss.starts_with("three")) { curr = Some(3); }
+    if ss.starts_with('4') || (use_words && ss.starts_with("four")) { curr = Some(4); }
+    if ss.starts_with('5') || (use_words && ss.starts_with("five")) { curr = Some(5); }
+    if ss.starts_with('6') || (use_words && ss.starts_with("six")) { curr = Some(6); }
+    if ss.starts_with('7') || (use_words && ss.starts_with("seven")) { curr = Some(7); }
+    if ss.starts_with('8') || (use_words && ss.starts_with("eight")) { curr = Some(8); }
+    if ss.starts_with('9') || (use_words && ss.starts_with("nine")) { curr = Some(9); }
+    if ss.starts_with('0') || (use_words && ss.starts_with("zero")) { curr = Some(0); }
+
+    if curr.is_some() {
+      last = curr;
+      if first.is_none() {
+        first = curr;
+      }
+    }
+  }
+
+  (first.unwrap_or(0), last.unwrap_or(0))
+}
+
+// The two digit number made of a line's first and last digits
+fn calibration(s: &str, use_words: bool) -> u32 {
+  let (first, last) = find_numbers(s, use_words);
+  first * 10 + last
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+  solve_part1_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part1`, but reads the input a line at a time.
+pub fn solve_part1_from<R: BufRead>(input: R) -> io::Result<String> {
+  shared::stream::sum_lines(input, |s| calibration(s, false) as u64).map(|n| n.to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+  solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+  solve_part2_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part2`, but reads the input a line at a time.
+pub fn solve_part2_from<R: BufRead>(input: R) -> io::Result<String> {
+  shared::stream::sum_lines(input, |s| calibration(s, true) as u64).map(|n| n.to_string())
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+  solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::prelude::*;
+
+  const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+  ];
+
+  proptest! {
+    #[test]
+    fn find_numbers_digits(first in 0u32..10, last in 0u32..10, filler in "[a-zé€]{0,16}", use_words: bool) {
+      let line = format!("{first}{filler}{last}");
+      prop_assert_eq!(super::find_numbers(&line, use_words), (first, last));
+    }
+
+    #[test]
+    fn find_numbers_words(first in 0usize..10, last in 0usize..10, filler in "[0-9a-z]{0,16}") {
+      let line = format!("{}{filler}{}", WORDS[first], WORDS[last]);
+      prop_assert_eq!(super::find_numbers(&line, true), (first as u32, last as u32));
+    }
+
+    #[test]
+    fn find_numbers_no_digits(line in "[a-z]{0,32}") {
+      let (first, last) = super::find_numbers(&line, false);
+      prop_assert_eq!((first, last), (0, 0));
+    }
+  }
+
+  #[test]
+  fn solve_part1() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part1(), "54667");
+  }
+
+  #[test]
+  fn solve_part2() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part2(), "54203");
+  }
+}
+
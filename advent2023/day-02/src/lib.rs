@@ -0,0 +1,216 @@
+//! Day 2: Cube Conundrum
+
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use nom::{
+  IResult,
+  bytes::complete::tag,
+  character::complete::alpha1,
+  multi::separated_list1,
+  sequence::tuple,
+};
+
+/// Cubes of each colour shown in a single handful. Colours that were
+/// not shown are zero.
+#[derive(Debug, PartialEq)]
+pub struct CubeReveal {
+  pub red: i32,
+  pub green: i32,
+  pub blue: i32,
+}
+
+/// One game record: its id and every handful revealed.
+#[derive(Debug, PartialEq)]
+pub struct CubeGame {
+  pub id: i32,
+  pub rev: Vec<CubeReveal>,
+}
+
+impl fmt::Display for CubeReveal {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let colors = [(self.red, "red"), (self.green, "green"), (self.blue, "blue")];
+    let shown = colors.iter()
+      .filter(|(n, _)| *n > 0)
+      .map(|(n, c)| format!("{n} {c}"))
+      .collect::<Vec<_>>();
+
+    write!(f, "{}", shown.join(", "))
+  }
+}
+
+impl fmt::Display for CubeGame {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let shown = self.rev.iter()
+      .map(|r| r.to_string())
+      .collect::<Vec<_>>();
+
+    write!(f, "Game {}: {}", self.id, shown.join("; "))
+  }
+}
+
+fn nom_i32(input: &str) -> IResult<&str, i32> {
+  nom::character::complete::i32(input)
+}
+
+// Counts are kept small enough that a game's power fits in an i64
+fn nom_count(input: &str) -> IResult<&str, i32> {
+  let (input, n) = nom::character::complete::u16(input)?;
+
+  Ok((input, n.into()))
+}
+
+fn nom_color_reveal(input: &str) -> IResult<&str, (i32, &str)> {
+  let (input, (num, _, col)) = tuple((nom_count, tag(" "), alpha1))(input)?;
+
+  Ok((input, (num, col)))
+}
+
+fn nom_cube_reveal(input: &str) -> IResult<&str, CubeReveal> {
+  let (input, v) = separated_list1(tag(", "), nom_color_reveal)(input)?;
+
+  let mut red: i32 = 0;
+  let mut green: i32 = 0;
+  let mut blue: i32 = 0;
+
+  for x in &v {
+    if x.1 == "red" { red = x.0; }
+    if x.1 == "green" { green = x.0; }
+    if x.1 == "blue" { blue = x.0; }
+  }
+
+  Ok((input, CubeReveal { red, green, blue }))
+}
+
+fn nom_cube_reveals(input: &str) -> IResult<&str, Vec<CubeReveal>> {
+  let (input, v) = separated_list1(tag("; "), nom_cube_reveal)(input)?;
+
+  Ok((input, v))
+}
+
+fn nom_game_id(input: &str) -> IResult<&str, i32> {
+  let (input, _) = tag("Game ")(input)?;
+  let (input, id) = nom_i32(input)?;
+  let (input, _) = tag(": ")(input)?;
+
+  Ok((input, id))
+}
+
+fn nom_game(input: &str) -> IResult<&str, CubeGame> {
+  let (input, id) = nom_game_id(input)?;
+  let (input, rev) = nom_cube_reveals(input)?;
+
+  Ok((input, CubeGame { id, rev }))
+}
+
+impl FromStr for CubeGame {
+  type Err = String;
+
+  /// Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    match nom_game(input) {
+      Ok((_, x)) => Ok(x),
+      _ => Err("Game could not be parsed".to_string()),
+    }
+  }
+}
+
+/// Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+///
+/// Panics if the line is not a valid game record.
+pub fn parse_game(input: &str) -> CubeGame {
+  input.parse().unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+  solve_part1_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part1`, but reads the input a line at a time.
+pub fn solve_part1_from<R: BufRead>(input: R) -> io::Result<String> {
+  shared::stream::fold_parsed(input, 0, |total, g: CubeGame| {
+    match g.rev.iter().all(|a| a.red <= 12 && a.green <= 13 && a.blue <= 14) {
+      true => total + g.id as i64,
+      false => total,
+    }
+  })
+  .map(|n| n.to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+  solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+  solve_part2_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part2`, but reads the input a line at a time.
+pub fn solve_part2_from<R: BufRead>(input: R) -> io::Result<String> {
+  shared::stream::fold_parsed(input, 0, |total, g: CubeGame| {
+    let max_red = g.rev.iter().map(|a| a.red).max().unwrap();
+    let max_green = g.rev.iter().map(|a| a.green).max().unwrap();
+    let max_blue = g.rev.iter().map(|a| a.blue).max().unwrap();
+    total + max_red as i64 * max_green as i64 * max_blue as i64
+  })
+  .map(|n| n.to_string())
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+  solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::prelude::*;
+
+  fn arb_reveal() -> impl Strategy<Value = CubeReveal> {
+    (0..20i32, 0..20i32, 0..20i32)
+      .prop_filter("at least one cube", |(r, g, b)| r + g + b > 0)
+      .prop_map(|(red, green, blue)| CubeReveal { red, green, blue })
+  }
+
+  fn arb_game() -> impl Strategy<Value = CubeGame> {
+    (1..1000i32, prop::collection::vec(arb_reveal(), 1..8))
+      .prop_map(|(id, rev)| CubeGame { id, rev })
+  }
+
+  proptest! {
+    #[test]
+    fn parse_game_roundtrip(game in arb_game()) {
+      prop_assert_eq!(parse_game(&game.to_string()), game);
+    }
+
+    #[test]
+    fn parse_game_rejects(line in "Game [0-9]{1,3}: [1-9][0-9]{5} red|Game: 1 red|Game 1 1 red|[a-z ]{0,12}") {
+      prop_assert!(line.parse::<CubeGame>().is_err());
+    }
+
+    #[test]
+    fn parse_game_any_color_order(red in 1..20i32, green in 1..20i32, blue in 1..20i32) {
+      let game = parse_game(&format!("Game 1: {blue} blue, {red} red, {green} green"));
+      prop_assert_eq!(game.rev, vec![CubeReveal { red, green, blue }]);
+    }
+  }
+
+  #[test]
+  fn solve_part1() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part1(), "2377");
+  }
+
+  #[test]
+  fn solve_part2() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part2(), "71220");
+  }
+}
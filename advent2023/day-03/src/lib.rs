@@ -0,0 +1,338 @@
+//! Day 3: Gear Ratios
+
+use std::fmt;
+
+/// A single cell of the schematic. `part` is set on the first digit of
+/// each part number, and `ratio` on each gear.
+#[derive(Debug)]
+pub struct EngineComponent {
+  pub code: char,
+  pub is_symbol: bool,
+  pub is_digit: bool,
+  pub part: u32,
+  pub ratio: u32,
+}
+
+/// The engine schematic, indexed by row then column.
+#[derive(Debug)]
+pub struct Engine {
+  pub schematic: Vec<Vec<EngineComponent>>
+}
+
+impl Engine {
+  /// Width of the widest row.
+  pub fn len_x(&self) -> usize {
+    self.schematic.iter().map(|x| x.len()).max().unwrap_or(0)
+  }
+  /// Number of rows.
+  pub fn len_y(&self) -> usize {
+    self.schematic.len()
+  }
+
+  pub fn is_symbol(&self, x: i32, y: i32) -> bool {
+    self.get(x, y).is_some_and(|x| x.is_symbol)
+  }
+
+  pub fn is_digit(&self, x: i32, y: i32) -> bool {
+    self.get(x, y).is_some_and(|x| x.is_digit)
+  }
+
+  fn near_symbol(&self, x: i32, y: i32) -> bool {
+    self.is_symbol(x - 1, y - 1) ||
+    self.is_symbol(x - 1, y) ||
+    self.is_symbol(x - 1, y + 1) ||
+    self.is_symbol(x, y - 1) ||
+    self.is_symbol(x, y + 1) ||
+    self.is_symbol(x + 1, y - 1) ||
+    self.is_symbol(x + 1, y) ||
+    self.is_symbol(x + 1, y + 1)
+  }
+
+  fn find_part_from(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+    if let Some(c) = self.get(x, y) {
+      if c.part > 0 {
+        return Some((x, y));
+      }
+      if c.is_digit {
+        return self.find_part_from(x - 1, y); 
+      }
+    }
+    None
+  }
+
+  // The number whose digits run right from `x`, and whether any of them
+  // is next to a symbol
+  fn test_part(&self, x: i32, y: i32) -> Option<(u32, bool)> {
+    let mut found: Option<(u32, bool)> = None;
+    let mut x = x;
+
+    while let Some(c) = self.get(x, y).filter(|c| c.is_digit) {
+      let (id, sym) = found.unwrap_or((0, false));
+      found = Some((id * 10 + c.code.to_digit(10).unwrap(), sym || self.near_symbol(x, y)));
+      x += 1;
+    }
+    found
+  }
+
+  fn test_ratio(&self, x: i32, y: i32) -> Option<u32> {
+    let mut parts: Vec<(i32,i32)> = vec![];
+
+    if let Some(c) = self.get(x, y) {
+      if c.is_symbol && c.code == '*' {
+        let n = self.get(x, y - 1);
+        let s = self.get(x, y + 1);
+
+        if n.is_some_and(|x| x.is_digit) {
+          if let Some(cn2) = self.find_part_from(x, y - 1) { parts.push(cn2); }
+        } else {
+          if let Some(cn1) = self.find_part_from(x - 1, y - 1) { parts.push(cn1); }
+          if let Some(cn3) = self.find_part_from(x + 1, y - 1) { parts.push(cn3); }
+        }
+
+        if s.is_some_and(|x| x.is_digit) {
+          if let Some(cs2) = self.find_part_from(x, y + 1) { parts.push(cs2); }
+        } else {
+          if let Some(cs1) = self.find_part_from(x - 1, y + 1) { parts.push(cs1); }
+          if let Some(cs3) = self.find_part_from(x + 1, y + 1) { parts.push(cs3); }
+        }
+
+        if let Some(ce) = self.find_part_from(x + 1, y) { parts.push(ce); }
+        if let Some(cw) = self.find_part_from(x - 1, y) { parts.push(cw); }
+
+        if parts.len() == 2 {
+          let p1 = self.get(parts[0].0, parts[0].1).unwrap();
+          let p2 = self.get(parts[1].0, parts[1].1).unwrap();
+          return Some(p1.part * p2.part);
+        }
+      }
+    }
+    None
+  }
+
+  /// Cell at column `x`, row `y`, or `None` off the edge.
+  pub fn get(&self, x: i32, y: i32) -> Option<&EngineComponent> {
+    if x < 0 || y < 0 { return None; }
+    if let Some(cy) = self.schematic.get(y as usize) {
+      return cy.get(x as usize);
+    }
+    None
+  }
+
+  fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut EngineComponent> {
+    if x < 0 || y < 0 { return None; }
+    if let Some(cy) = self.schematic.get_mut(y as usize) {
+      return cy.get_mut(x as usize);
+    }
+    None
+  }
+}
+
+impl fmt::Display for Engine {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let rows = self.schematic.iter()
+      .map(|r| r.iter().map(|c| c.code).collect::<String>())
+      .collect::<Vec<_>>();
+
+    write!(f, "{}", rows.join("\n"))
+  }
+}
+
+impl From<&[&str]> for Engine {
+    /// Panics if the schematic is not valid; see `Engine::try_parse`.
+    fn from(item: &[&str]) -> Self {
+      Engine::try_parse(item).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Engine {
+    /// Builds the engine from its schematic rows. Part numbers longer than
+    /// four digits are rejected, so that gear ratios fit in a u32.
+    pub fn try_parse(item: &[&str]) -> Result<Self, String> {
+      fn is_digit(ch: char) -> bool { "0123456789".contains(ch) }
+      fn is_symbol(ch: char) -> bool { "@#$%&*-+=/".contains(ch) }
+
+      fn calc_schematic(eng: &mut Engine, items: &[&str]) {
+        eng.schematic = 
+          items.iter()
+          .map(|x| {
+            x.chars()
+            .map(|ch| EngineComponent {
+              code: ch,
+              is_symbol: is_symbol(ch),
+              is_digit: is_digit(ch),
+              part: 0,
+              ratio: 0,
+            }).collect()
+          }).collect()        
+      }
+
+      fn calc_parts(eng: &mut Engine) {
+        for j in 0..eng.len_y() {
+          let jj = j as i32;
+          for i in 0..eng.len_x() {
+            let ii = i as i32;
+            if let Some(t0) = eng.test_part(ii, jj) {
+              if t0.1 && !eng.is_digit(ii - 1, jj) {
+                //println!("test_part({ii},{jj}) => {t0:?}");
+                let c0 = eng.get_mut(ii, jj).unwrap();
+                c0.part = t0.0;
+              }
+            }
+          }
+        }
+      }
+
+      fn calc_gears(eng: &mut Engine) {
+        for j in 0..eng.len_y() {
+          let jj = j as i32;
+          for i in 0..eng.len_x() {
+            let ii = i as i32;
+            if let Some(t0) = eng.test_ratio(ii, jj) {
+              //println!("test_ratio({ii},{jj}) => {t0:?}");
+              let c0 = eng.get_mut(ii, jj).unwrap();
+              c0.ratio = t0;
+            }
+          }
+        }
+      }
+
+      for (y, line) in item.iter().enumerate() {
+        if line.split(|ch| !is_digit(ch)).any(|n| n.len() > 4) {
+          return Err(format!("line {}: part number is too long", y + 1));
+        }
+      }
+
+      let mut eng = Engine {
+        schematic: vec![],
+      };
+      calc_schematic(&mut eng, item);
+      calc_parts(&mut eng);
+      calc_gears(&mut eng);
+
+      Ok(eng)
+    }
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+  let lines = shared::input_as_lines(input);
+  let eng = Engine::from(lines.as_slice());
+  
+  eng.schematic.iter()
+    .flatten()
+    .map(|x| u64::from(x.part))
+    .sum::<u64>()
+    .to_string()
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+  solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+  let lines = shared::input_as_lines(input);
+  let eng = Engine::from(lines.as_slice());
+
+  eng.schematic.iter()
+    .flatten()
+    .map(|x| u64::from(x.ratio))
+    .sum::<u64>()
+    .to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+  solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::prelude::*;
+
+  fn parse_engine<S: AsRef<str>>(lines: &[S]) -> Engine {
+    lines.iter().map(|l| l.as_ref()).collect::<Vec<_>>().as_slice().into()
+  }
+
+  fn total_parts(eng: &Engine) -> u32 {
+    eng.schematic.iter().flatten().map(|x| x.part).sum()
+  }
+
+  fn total_ratios(eng: &Engine) -> u32 {
+    eng.schematic.iter().flatten().map(|x| x.ratio).sum()
+  }
+
+  proptest! {
+    // Rows are kept narrow so part numbers and gear ratios fit in a u32
+    #[test]
+    fn parse_engine_roundtrip(lines in prop::collection::vec("[.0-9*#+]{4}", 1..8)) {
+      let eng = parse_engine(&lines);
+      prop_assert_eq!(eng.to_string(), lines.join("\n"));
+    }
+
+    #[test]
+    fn no_symbols_no_parts(lines in prop::collection::vec("[.0-9]{1,4}(\\.[.0-9]{0,4})?", 1..8)) {
+      let eng = parse_engine(&lines);
+      prop_assert_eq!(total_parts(&eng), 0);
+      prop_assert_eq!(total_ratios(&eng), 0);
+    }
+
+    #[test]
+    fn long_part_rejected(pre in "[.*]{0,3}", n in 10000..u64::MAX) {
+      let line = format!("{pre}{n}*");
+      prop_assert!(Engine::try_parse([line.as_str()].as_slice()).is_err());
+    }
+
+    #[test]
+    fn gear_between_parts(a in 1..1000u32, b in 1..1000u32, c in 1..1000u32, sym in "[@#$%&+=/-]") {
+      let lines = vec![
+        format!("..{a}*{b}.."),
+        "..........".to_string(),
+        format!("{c}{sym}......"),
+      ];
+      let eng = parse_engine(&lines);
+      prop_assert_eq!(total_parts(&eng), a + b + c);
+      prop_assert_eq!(total_ratios(&eng), a * b);
+    }
+  }
+
+  // The schematic, then where each part number and gear ratio was found
+  fn render_engine(eng: &Engine) -> String {
+    let mut out = format!("{eng}\n\nparts:\n");
+    let cells = eng.schematic.iter().enumerate()
+      .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, c)| ((x, y), c)));
+
+    for ((x, y), c) in cells.clone().filter(|(_, c)| c.part > 0) {
+      out += &format!("  ({x}, {y}) {}\n", c.part);
+    }
+    out += "gears:\n";
+    for ((x, y), c) in cells.filter(|(_, c)| c.ratio > 0) {
+      out += &format!("  ({x}, {y}) {}\n", c.ratio);
+    }
+    out
+  }
+
+  #[test]
+  fn snapshot_samples() {
+    let eng = parse_engine(&shared::input_as_lines(include_str!("../input2.txt")));
+    insta::assert_snapshot!("engine_input2", render_engine(&eng));
+  }
+
+  #[test]
+  fn solve_part1() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part1(), "550064");
+  }
+
+  #[test]
+  fn solve_part2() {
+    if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+      return;
+    }
+    assert_eq!(super::part2(), "85010461");
+  }
+}
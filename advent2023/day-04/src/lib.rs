@@ -0,0 +1,243 @@
+//! Day 4: Scratchcards
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead};
+use shared::parse::AocParse;
+
+/// A scratchcard: its winning numbers and the numbers you have.
+#[derive(Debug, AocParse)]
+#[aoc("Card {id}: {winners} | {numbers}")]
+pub struct Card {
+    pub id: u32,
+    pub winners: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    /// Numbers you have that are also winning numbers.
+    pub fn matches(&self) -> Vec<u32> {
+        self.numbers.iter().filter(|x| self.winners.contains(x)).cloned().collect::<Vec<_>>()
+    }
+
+    /// One point for the first match, doubled for each match after, or
+    /// `None` with too many matches for the points to fit in a u64.
+    pub fn points(&self) -> Option<u64> {
+        let m = self.matches();
+        if !m.is_empty() {
+            return u32::try_from(m.len() - 1).ok().and_then(|n| 1u64.checked_shl(n));
+        }
+        Some(0)
+    }
+
+    /// Total cards this card ends up as, counting itself and every copy
+    /// it wins. `cards` is the whole table in id order, and `memo` comes
+    /// from [`Card::create_memo`].
+    pub fn wins(&self, cards: &Vec<Card>, memo: &mut Vec<i32>) -> u32 {
+        let card_id = self.id as usize;
+        
+        //println!["card id={card_id}"];
+        if memo[card_id] < 0 {
+            //println!("card {} - calculating", card_id);
+            let mut w: u32 = 1;
+            let mlen = self.matches().len() as u32;
+            if mlen > 0 {
+                let m_start = self.id + 1;
+                let m_finish = self.id + 1 + mlen;
+                for i in m_start..m_finish {
+                    w += cards[(i - 1) as usize].wins(cards, memo)
+                }
+            }
+            memo[card_id] = w as i32;
+        }
+
+        //println!("card {} result = {}", card_id, memo[card_id] as u64);
+        memo[card_id] as u32
+    }
+
+    /// Empty memo for [`Card::wins`] over `len` cards.
+    pub fn create_memo(len: usize) -> Vec<i32> {
+        (0..(len + 1)).map(|_| -1).collect::<Vec<_>>()
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |v: &[u32]| v.iter().map(|x| format!("{x:>2}")).collect::<Vec<_>>().join(" ");
+
+        write!(f, "Card {:>3}: {} | {}", self.id, join(&self.winners), join(&self.numbers))
+    }
+}
+
+impl From<&str> for Card {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+const TOO_BIG: &str = "the answer overflows a u64";
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    solve_part1_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part1`, but reads the input a line at a time.
+pub fn solve_part1_from<R: BufRead>(input: R) -> io::Result<String> {
+    shared::stream::try_fold_parsed(input, 0u64, |total, card: Card| {
+        card.points().and_then(|p| total.checked_add(p)).ok_or(TOO_BIG)
+    })
+    .map(|n| n.to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    solve_part2_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part2`, but reads the input a line at a time. Only the
+/// copies won of the next few cards are kept, rather than every card.
+pub fn solve_part2_from<R: BufRead>(input: R) -> io::Result<String> {
+    // Copies won so far of each card after the current one
+    let mut ahead = VecDeque::<u64>::new();
+
+    shared::stream::try_fold_parsed(input, 0u64, |total, card: Card| {
+        let copies = ahead.pop_front().unwrap_or(0).checked_add(1).ok_or(TOO_BIG)?;
+        for i in 0..card.matches().len() {
+            match ahead.get_mut(i) {
+                Some(n) => *n = n.checked_add(copies).ok_or(TOO_BIG)?,
+                None => ahead.push_back(copies),
+            }
+        }
+        total.checked_add(copies).ok_or(TOO_BIG)
+    })
+    .map(|n| n.to_string())
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn arb_card() -> impl Strategy<Value = Card> {
+        (1..300u32, prop::collection::vec(1..100u32, 1..10), prop::collection::vec(1..100u32, 1..25))
+            .prop_map(|(id, winners, numbers)| Card { id, winners, numbers })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_card_roundtrip(card in arb_card()) {
+            let parsed = Card::from(card.to_string().as_str());
+
+            prop_assert_eq!(parsed.id, card.id);
+            prop_assert_eq!(parsed.winners, card.winners);
+            prop_assert_eq!(parsed.numbers, card.numbers);
+        }
+
+        #[test]
+        fn too_many_points(winners in prop::collection::hash_set(1..1000u32, 65..100)) {
+            let winners = winners.into_iter().collect::<Vec<_>>();
+            let card = Card { id: 1, numbers: winners.clone(), winners };
+            prop_assert_eq!(card.points(), None);
+        }
+
+        #[test]
+        fn points_double_per_match(nums in prop::collection::hash_set(1..100u32, 2..30), k in 0usize..10) {
+            let nums = nums.into_iter().collect::<Vec<_>>();
+            let (winners, rest) = nums.split_at(nums.len() / 2);
+            let k = k.min(winners.len());
+            let numbers = [&winners[..k], rest].concat();
+
+            let card = Card { id: 1, winners: winners.to_vec(), numbers };
+            let expected = if k == 0 { 0 } else { 1 << (k - 1) };
+
+            prop_assert_eq!(card.matches().len(), k);
+            prop_assert_eq!(card.points(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn parse_input1() {
+        let card: Card = 
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".into();
+
+        assert_eq!(card.id, 1);
+        assert_eq!(card.winners, vec![41, 48, 83, 86, 17]);
+        assert_eq!(card.numbers, vec![83, 86, 6, 31, 17, 9, 48, 53])
+    }
+
+    #[test]
+    fn solve_sample1() {
+        let lines = vec![
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",          
+        ];
+
+        let cards: Vec<Card> = lines.into_iter().map(|x| x.into()).collect();
+
+        assert_eq![cards[0].points(), Some(8)];
+        assert_eq![cards[1].points(), Some(2)];
+        assert_eq![cards[2].points(), Some(2)];
+        assert_eq![cards[3].points(), Some(1)];
+        assert_eq![cards[4].points(), Some(0)];
+        assert_eq![cards[5].points(), Some(0)];
+    }
+
+    #[test]
+    fn solve_sample2() {
+        let lines = vec![
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",          
+        ];
+
+        let cards: Vec<Card> = lines.into_iter().map(|x| x.into()).collect();
+        let mut memo = Card::create_memo(cards.len());
+
+        let c6 = cards[5].wins(&cards, &mut memo);
+        assert_eq![c6, 1];
+        let c5 = cards[4].wins(&cards, &mut memo);
+        assert_eq![c5, 1];
+        let c4 = cards[3].wins(&cards, &mut memo);
+        assert_eq![c4, 2];
+        let c3 = cards[2].wins(&cards, &mut memo);
+        assert_eq![c3, 4];
+        let c2 = cards[1].wins(&cards, &mut memo);
+        assert_eq![c2, 7];
+        let c1 = cards[0].wins(&cards, &mut memo);
+        assert_eq![c1, 15];
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "28538");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "9425061");
+    }
+}
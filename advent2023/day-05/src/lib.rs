@@ -0,0 +1,605 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use std::fmt;
+use std::str::FromStr;
+use itertools::Itertools;
+use shared::cancel::{Cancel, Cancelled};
+
+// Seeds walked between checks for cancellation
+const CHECK_EVERY: i64 = 1 << 20;
+
+/// One line of an almanac map: `len` values starting at `src` map onto
+/// the same number of values starting at `dest`.
+#[derive(Debug, PartialEq)]
+pub struct GardenMap {
+    pub src: i64,
+    pub dest: i64,
+    pub len: i64,
+}
+
+impl From<&str> for GardenMap {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl FromStr for GardenMap {
+    type Err = String;
+
+    /// Parses a line like `50 98 2`: the destination start, the source
+    /// start, then the length. Neither range may run past `i64::MAX`.
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let nums = item.split(' ')
+            .map(|x| x.parse::<i64>().ok().filter(|&n| n >= 0))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Unable to parse map number")?;
+        let [dest, src, len] = nums[..] else {
+            return Err("Map line needs three numbers".to_string());
+        };
+        if src.checked_add(len).is_none() || dest.checked_add(len).is_none() {
+            return Err("Map range is too long".to_string());
+        }
+
+        Ok(GardenMap {
+            src,
+            dest,
+            len,
+        })
+    }
+}
+
+impl fmt::Display for GardenMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.dest, self.src, self.len)
+    }
+}
+
+/// The whole almanac: seeds to plant and each stage's maps.
+#[derive(Debug, Default)]
+pub struct Garden {
+    pub seeds: Vec<i64>,
+    pub seed_to_soil: Vec<GardenMap>,
+    pub soil_to_fert: Vec<GardenMap>,
+    pub fert_to_water: Vec<GardenMap>,
+    pub water_to_light: Vec<GardenMap>,
+    pub light_to_temp: Vec<GardenMap>,
+    pub temp_to_humid: Vec<GardenMap>,
+    pub humid_to_loc: Vec<GardenMap>,
+}
+
+impl Garden {
+    /// An almanac with no seeds and no maps.
+    pub fn new() -> Self {
+        Self {
+            seeds: vec![],
+            seed_to_soil: vec![],
+            soil_to_fert: vec![],
+            fert_to_water: vec![],
+            water_to_light: vec![],
+            light_to_temp: vec![],
+            temp_to_humid: vec![],
+            humid_to_loc: vec![],
+        }
+    }
+
+    /// Maps `x` through one stage. Values outside every range map to
+    /// themselves.
+    pub fn eval_map(&self, x: i64, gmaps: &Vec<GardenMap>) -> i64 {
+        for gmap in gmaps {
+            if x >= gmap.src && x < gmap.src + gmap.len {
+                return x - gmap.src + gmap.dest;
+            }
+        }
+        x
+    }
+
+    /// Location for a single seed.
+    pub fn find_loc_part1(&self, seed: i64) -> i64 {
+        let mut r: i64 = seed;
+        r = self.eval_map(r, &self.seed_to_soil);
+        r = self.eval_map(r, &self.soil_to_fert);
+        r = self.eval_map(r, &self.fert_to_water);
+        r = self.eval_map(r, &self.water_to_light);
+        r = self.eval_map(r, &self.light_to_temp);
+        r = self.eval_map(r, &self.temp_to_humid);
+        r = self.eval_map(r, &self.humid_to_loc);
+
+        r
+    }
+
+    /// Lowest location for the `len` seeds starting at `start`, or None
+    /// if there are no seeds in the range.
+    pub fn find_loc_part2(&self, start: i64, len: i64) -> Option<i64> {
+        self.find_loc_part2_with(start, len, |_| Ok(())).unwrap()
+    }
+
+    /// Like `find_loc_part2`, but calls `tick` with the number of seeds
+    /// walked so far every so often, and stops if it returns an error.
+    pub fn find_loc_part2_with(
+        &self,
+        start: i64,
+        len: i64,
+        mut tick: impl FnMut(i64) -> Result<(), Cancelled>,
+    ) -> Result<Option<i64>, Cancelled> {
+
+        let mut m: Option<i64> = None;
+
+        for seed in start..(start+len) {
+            if (seed - start) % CHECK_EVERY == 0 {
+                tick(seed - start)?;
+            }
+
+            let mut r: i64 = seed;
+            r = self.eval_map(r, &self.seed_to_soil);
+            r = self.eval_map(r, &self.soil_to_fert);
+            r = self.eval_map(r, &self.fert_to_water);
+            r = self.eval_map(r, &self.water_to_light);
+            r = self.eval_map(r, &self.light_to_temp);
+            r = self.eval_map(r, &self.temp_to_humid);
+            r = self.eval_map(r, &self.humid_to_loc);
+    
+            m = Some(m.map_or(r, |m| m.min(r)));
+        }
+        Ok(m)
+    }
+
+    /// Lowest location for the `len` seeds starting at `start`, mapping
+    /// whole ranges of seeds at once instead of walking each one.
+    pub fn find_loc_part2_intervals(&self, start: i64, len: i64) -> Option<i64> {
+        let stages = [
+            &self.seed_to_soil,
+            &self.soil_to_fert,
+            &self.fert_to_water,
+            &self.water_to_light,
+            &self.light_to_temp,
+            &self.temp_to_humid,
+            &self.humid_to_loc,
+        ];
+
+        stages.into_iter()
+            .fold(vec![(start, start + len)], |spans, gmaps| self.eval_map_spans(spans, gmaps))
+            .into_iter()
+            .map(|(s, _)| s)
+            .min()
+    }
+
+    /// Maps half-open spans through one stage, splitting them wherever
+    /// they cross the edge of a range. Empty spans are dropped.
+    pub fn eval_map_spans(&self, spans: Vec<(i64, i64)>, gmaps: &Vec<GardenMap>) -> Vec<(i64, i64)> {
+        let mut done = vec![];
+        let mut todo = spans.into_iter().filter(|(s, e)| s < e).collect::<Vec<_>>();
+
+        for gmap in gmaps {
+            let mut rest = vec![];
+            for (s, e) in todo {
+                let (a, b) = (s.max(gmap.src), e.min(gmap.src + gmap.len));
+                if a < b {
+                    done.push((a - gmap.src + gmap.dest, b - gmap.src + gmap.dest));
+                    if s < a { rest.push((s, a)); }
+                    if b < e { rest.push((b, e)); }
+                } else {
+                    rest.push((s, e));
+                }
+            }
+            todo = rest;
+        }
+
+        done.extend(todo);
+        done
+    }
+}
+
+impl From<&[&str]> for Garden {
+    /// Panics if the almanac is not valid; see `Garden::try_parse`.
+    fn from(item: &[&str]) -> Self {
+      Garden::try_parse(item).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Garden {
+    /// Builds the almanac from its lines. There must be at least one pair
+    /// of seeds, and no seed range may run past `i64::MAX`.
+    pub fn try_parse(item: &[&str]) -> Result<Self, String> {
+      let mut g = Garden::new();
+
+      let mut i: usize = 0;
+      let mut v: Vec<&mut Vec<GardenMap>> = vec![
+        &mut g.seed_to_soil,
+        &mut g.soil_to_fert,
+        &mut g.fert_to_water,
+        &mut g.water_to_light,
+        &mut g.light_to_temp,
+        &mut g.temp_to_humid,
+        &mut g.humid_to_loc,
+      ];
+
+
+      for &line in item {
+        if line.is_empty() { continue; }
+
+        if let Some(seeds) = line.strip_prefix("seeds: ") {
+            g.seeds = seeds.split(' ')
+                .map(|x| x.parse::<i64>().ok().filter(|&n| n >= 0))
+                .collect::<Option<Vec<_>>>()
+                .ok_or("Unable to parse seeds")?;
+            continue;
+        }
+
+        if line.starts_with("seed-to-soil") ||
+           line.starts_with("soil-to-fertilizer") ||
+           line.starts_with("fertilizer-to-water") ||
+           line.starts_with("water-to-light") ||
+           line.starts_with("light-to-temperature") ||
+           line.starts_with("temperature-to-humidity") ||
+           line.starts_with("humidity-to-location") {
+           i += 1; continue;
+        }
+
+        if i == 0 {
+            return Err("Map line before any map header".to_string());
+        }
+        if let Some(vref) = v.get_mut(i-1) {
+            vref.push(line.parse()?);
+            //let g0 = GardenMap::from(line);
+            //dbg![g0];
+            //dbg![i];
+        }
+      }
+
+      if g.seeds.is_empty() || !g.seeds.len().is_multiple_of(2) {
+        return Err("Seeds must come in pairs".to_string());
+      }
+      if g.seeds.iter().tuples::<(_, _)>().any(|(start, len)| start.checked_add(*len).is_none()) {
+        return Err("Seed range is too long".to_string());
+      }
+
+      Ok(g)
+    }
+}
+
+impl fmt::Display for Garden {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "seeds: {}", self.seeds.iter().join(" "))?;
+
+        let sections = [
+            ("seed-to-soil", &self.seed_to_soil),
+            ("soil-to-fertilizer", &self.soil_to_fert),
+            ("fertilizer-to-water", &self.fert_to_water),
+            ("water-to-light", &self.water_to_light),
+            ("light-to-temperature", &self.light_to_temp),
+            ("temperature-to-humidity", &self.temp_to_humid),
+            ("humidity-to-location", &self.humid_to_loc),
+        ];
+
+        for (name, gmaps) in sections {
+            write!(f, "\n{name} map:\n")?;
+            for gmap in gmaps {
+                writeln!(f, "{gmap}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    let lines = shared::input_as_lines(input);
+    let g: Garden = Garden::from(lines.as_slice());
+
+    let m = g.seeds
+        .iter()
+        .map(|s| g.find_loc_part1(*s))
+        .min().unwrap();
+
+    m.to_string()
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input by brute force, walking
+/// every seed in every range.
+pub fn solve_part2(input: &str) -> String {
+    solve_part2_with(input, &Cancel::default()).unwrap()
+}
+
+/// Like `solve_part2`, but reports progress through every range of
+/// seeds, and stops once `cancel` is cancelled.
+pub fn solve_part2_with(input: &str, cancel: &Cancel) -> Result<String, Cancelled> {
+    let lines = shared::input_as_lines(input);
+    let g: Garden = Garden::from(lines.as_slice());
+
+    let ranges = g.seeds.iter().copied().tuples::<(_,_)>().collect::<Vec<_>>();
+    let total = ranges.iter().map(|r| r.1).sum::<i64>().max(1) as f64;
+
+    let mut walked = 0;
+    let mut locs = vec![];
+    for (start, len) in ranges {
+        locs.push(g.find_loc_part2_with(start, len, |n| {
+            cancel.check()?;
+            cancel.progress((walked + n) as f64 / total);
+            Ok(())
+        })?);
+        walked += len;
+    }
+
+    Ok(locs.into_iter().flatten().min().unwrap_or(0).to_string())
+}
+
+/// Solves part 2 for the given puzzle input, mapping each range of
+/// seeds through the almanac as a whole.
+pub fn solve_part2_intervals(input: &str) -> String {
+    let lines = shared::input_as_lines(input);
+    let g: Garden = Garden::from(lines.as_slice());
+
+    let m = g.seeds.iter().copied()
+        .tuples::<(_,_)>()
+        .filter_map(|(start, len)| g.find_loc_part2_intervals(start, len))
+        .min().unwrap_or(0);
+
+    m.to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    let input = shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap();
+    solve_part2_intervals(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // Maps with disjoint source ranges and disjoint destination ranges,
+    // the destinations laid out in a shuffled order.
+    fn arb_maps() -> impl Strategy<Value = Vec<GardenMap>> {
+        prop::collection::vec((1..20i64, 0..5i64), 1..6)
+            .prop_flat_map(|spans| {
+                let order = (0..spans.len()).collect::<Vec<_>>();
+                (Just(spans), Just(order).prop_shuffle(), 0..1000i64, 0..1000i64)
+            })
+            .prop_map(|(spans, order, src0, dest0)| {
+                let mut srcs = vec![0; spans.len()];
+                let mut dests = vec![0; spans.len()];
+
+                let mut at = src0;
+                for (i, (len, gap)) in spans.iter().enumerate() {
+                    srcs[i] = at;
+                    at += len + gap;
+                }
+
+                let mut at = dest0;
+                for i in order {
+                    dests[i] = at;
+                    at += spans[i].0 + spans[i].1;
+                }
+
+                spans.iter()
+                    .enumerate()
+                    .map(|(i, (len, _))| GardenMap { src: srcs[i], dest: dests[i], len: *len })
+                    .collect()
+            })
+    }
+
+    fn arb_garden() -> impl Strategy<Value = Garden> {
+        (prop::collection::vec((0..1000i64, 0..1000i64), 1..5), prop::collection::vec(arb_maps(), 7))
+            .prop_map(|(seeds, mut sections)| {
+                let mut g = Garden::new();
+                g.seeds = seeds.into_iter().flat_map(|(s, len)| [s, len]).collect();
+                g.humid_to_loc = sections.pop().unwrap();
+                g.temp_to_humid = sections.pop().unwrap();
+                g.light_to_temp = sections.pop().unwrap();
+                g.water_to_light = sections.pop().unwrap();
+                g.fert_to_water = sections.pop().unwrap();
+                g.soil_to_fert = sections.pop().unwrap();
+                g.seed_to_soil = sections.pop().unwrap();
+                g
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_map_roundtrip(dest in 0..i64::MAX / 2, src in 0..i64::MAX / 2, len in 1..1000i64) {
+            let gmap = GardenMap { src, dest, len };
+            prop_assert_eq!(GardenMap::from(gmap.to_string().as_str()), gmap);
+        }
+
+        #[test]
+        fn parse_map_rejects(line in "-[1-9][0-9]{0,2} 1 1|1 1|1 1 1 1|[1-9][0-9]{19} 0 1|9223372036854775807 0 1") {
+            prop_assert!(line.parse::<GardenMap>().is_err());
+        }
+
+        #[test]
+        fn parse_garden_rejects(seeds in "|1|1 2 3|9223372036854775807 1") {
+            let text = format!("seeds: {seeds}\n\nseed-to-soil map:\n1 2 3");
+            prop_assert!(Garden::try_parse(&shared::input_as_lines(&text)).is_err());
+            prop_assert!(Garden::try_parse(&["seeds: 1 2", "1 2 3"]).is_err());
+        }
+
+        #[test]
+        fn parse_garden_roundtrip(g in arb_garden()) {
+            let text = g.to_string();
+            let lines = shared::input_as_lines(&text);
+            let parsed = Garden::from(lines.as_slice());
+
+            prop_assert_eq!(parsed.seeds, g.seeds);
+            prop_assert_eq!(parsed.seed_to_soil, g.seed_to_soil);
+            prop_assert_eq!(parsed.soil_to_fert, g.soil_to_fert);
+            prop_assert_eq!(parsed.fert_to_water, g.fert_to_water);
+            prop_assert_eq!(parsed.water_to_light, g.water_to_light);
+            prop_assert_eq!(parsed.light_to_temp, g.light_to_temp);
+            prop_assert_eq!(parsed.temp_to_humid, g.temp_to_humid);
+            prop_assert_eq!(parsed.humid_to_loc, g.humid_to_loc);
+        }
+
+        #[test]
+        fn eval_map_bijective_on_ranges(gmaps in arb_maps()) {
+            let g = Garden::new();
+            let mut seen: HashSet<i64> = HashSet::new();
+            let mut covered = 0;
+
+            for gmap in &gmaps {
+                for k in 0..gmap.len {
+                    let r = g.eval_map(gmap.src + k, &gmaps);
+                    prop_assert_eq!(r, gmap.dest + k);
+                    seen.insert(r);
+                    covered += 1;
+                }
+            }
+
+            let dests = gmaps.iter()
+                .flat_map(|m| m.dest..(m.dest + m.len))
+                .collect::<HashSet<_>>();
+
+            prop_assert_eq!(seen.len(), covered);
+            prop_assert_eq!(seen, dests);
+        }
+
+        #[test]
+        fn eval_map_identity_outside_ranges(gmaps in arb_maps(), x in -1000..3000i64) {
+            let g = Garden::new();
+            prop_assume!(gmaps.iter().all(|m| x < m.src || x >= m.src + m.len));
+            prop_assert_eq!(g.eval_map(x, &gmaps), x);
+        }
+    }
+
+    #[test]
+    fn parse_sample() {
+        let input = include_str!("../input2.txt");
+        let lines = shared::input_as_lines(input);
+
+        let g: Garden = Garden::from(lines.as_slice());
+
+        assert_eq!(g.seeds.len(), 4);
+        assert_eq!(g.seeds[0], 79);
+        assert_eq!(g.seeds[1], 14);
+        assert_eq!(g.seeds[2], 55);
+        assert_eq!(g.seeds[3], 13);
+
+        assert_eq!(g.seed_to_soil.len(), 2);
+        assert_eq!(g.soil_to_fert.len(), 3);
+        assert_eq!(g.fert_to_water.len(), 4);
+        assert_eq!(g.water_to_light.len(), 2);
+        assert_eq!(g.light_to_temp.len(), 3);
+        assert_eq!(g.temp_to_humid.len(), 2);
+        assert_eq!(g.humid_to_loc.len(), 2);
+
+        assert_eq!(g.seed_to_soil[0].src, 98);
+        assert_eq!(g.seed_to_soil[0].dest, 50);
+        assert_eq!(g.seed_to_soil[0].len, 2);
+
+        assert_eq!(g.find_loc_part1(g.seeds[0]), 82);
+        assert_eq!(g.find_loc_part1(g.seeds[1]), 43);
+        assert_eq!(g.find_loc_part1(g.seeds[2]), 86);
+        assert_eq!(g.find_loc_part1(g.seeds[3]), 35);
+
+        assert_eq!(g.find_loc_part2(g.seeds[0], g.seeds[1]), Some(46));
+        assert_eq!(g.find_loc_part2(g.seeds[2], g.seeds[3]), Some(56));
+    }
+
+    #[test]
+    fn cancel_brute_force() {
+        let input = include_str!("../input2.txt");
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let sink = seen.clone();
+        let cancel = Cancel::default().with_progress(move |f| sink.lock().unwrap().push(f));
+
+        assert_eq!(solve_part2_with(input, &cancel), Ok("46".to_string()));
+        assert_eq!(*seen.lock().unwrap(), vec![0.0, 14.0 / 27.0]);
+
+        cancel.cancel();
+        assert_eq!(solve_part2_with(input, &cancel), Err(Cancelled));
+    }
+
+    #[test]
+    fn brute_force_within_budget() {
+        use shared::cancel::{within, Outcome};
+        use std::time::Duration;
+
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        let input = shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap();
+        let cancel = Cancel::default();
+
+        let outcome = within(Duration::from_millis(50), &cancel, move |c| solve_part2_with(&input, c));
+        assert_eq!(outcome, Outcome::TimedOut);
+        assert!(cancel.is_cancelled());
+    }
+
+    // The parsed almanac, then each seed's number after every map, and the
+    // spans each seed range becomes
+    fn render_stages(input: &str) -> String {
+        let g = Garden::from(shared::input_as_lines(input).as_slice());
+        let maps = [
+            &g.seed_to_soil,
+            &g.soil_to_fert,
+            &g.fert_to_water,
+            &g.water_to_light,
+            &g.light_to_temp,
+            &g.temp_to_humid,
+            &g.humid_to_loc,
+        ];
+        let mut out = format!("{g}\n");
+
+        for &seed in &g.seeds {
+            let stages = maps.iter().scan(seed, |x, m| { *x = g.eval_map(*x, m); Some(*x) });
+            out += &format!("seed {seed}: {}\n", std::iter::once(seed).chain(stages).join(" -> "));
+        }
+        for pair in g.seeds.chunks(2) {
+            let mut spans = vec![(pair[0], pair[0] + pair[1])];
+            out += &format!("\nseeds {}..{}:\n", pair[0], pair[0] + pair[1]);
+            for m in maps {
+                spans = g.eval_map_spans(spans, m);
+                spans.sort();
+                out += &format!("  {}\n", spans.iter().map(|(s, e)| format!("{s}..{e}")).join(" "));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn snapshot_samples() {
+        insta::assert_snapshot!("stages_input2", render_stages(include_str!("../input2.txt")));
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "579439039");
+    }
+
+    #[test]
+    fn solve_sample_intervals() {
+        let input = include_str!("../input2.txt");
+        let g = Garden::from(shared::input_as_lines(input).as_slice());
+
+        assert_eq!(g.find_loc_part2_intervals(g.seeds[0], g.seeds[1]), Some(46));
+        assert_eq!(g.find_loc_part2_intervals(g.seeds[2], g.seeds[3]), Some(56));
+        assert_eq!(solve_part2_intervals(input), "46");
+    }
+
+    #[test]
+    fn lowest_location_and_empty_ranges() {
+        let map = "\n\nseed-to-soil map:\n100 200 1";
+        for (seeds, expected) in [("0 3", "0"), ("5 0", "0"), ("5 0 10 3", "10")] {
+            let input = format!("seeds: {seeds}{map}");
+            assert_eq!(super::solve_part2(&input), expected, "{seeds}");
+            assert_eq!(solve_part2_intervals(&input), expected, "{seeds}");
+        }
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "7873084");
+    }
+}
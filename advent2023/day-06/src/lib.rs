@@ -0,0 +1,186 @@
+//! Day 6: Wait For It
+
+/// A race lasting `time` milliseconds, with a record of `dist` millimeters.
+pub struct BoatRace {
+    pub time: i64,
+    pub dist: i64,
+}
+
+impl BoatRace {
+    /// Number of button hold times that beat the record.
+    pub fn find_wins(&self) -> i64 {
+        let (t, d) = (self.time as i128, self.dist as i128);
+        let dist = |hold: i128| hold * (t - hold);
+
+        // The distance rises up to a hold of half the race, then falls
+        // away the same, so the wins are the holds either side of the
+        // first one that beats the record
+        if t < 2 || dist(t / 2) <= d {
+            return 0;
+        }
+        let (mut lo, mut hi) = (1, t / 2);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if dist(mid) > d { hi = mid; } else { lo = mid + 1; }
+        }
+        (t - 2 * lo + 1) as i64
+    }
+
+    /// Parses the `Time:` and `Distance:` lines, one race per column.
+    pub fn parse_races(input: &str) -> Vec<BoatRace> {
+        BoatRace::try_parse_races(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like `parse_races`, but gives back an error for bad input.
+    pub fn try_parse_races(input: &str) -> Result<Vec<BoatRace>, String> {
+        let lines = shared::input_as_lines(input);
+        let nums = |s: &str| {
+            s.split_whitespace()
+                .skip(1)
+                .map(|x| x.parse::<i64>().map_err(|_| "Unable to parse number".to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let [time, dist, ..] = lines[..] else {
+            return Err("Expected time and distance lines".to_string());
+        };
+
+        Ok(nums(time)?.into_iter()
+            .zip(nums(dist)?)
+            .map(|(time, dist)| BoatRace { time, dist })
+            .collect())
+    }
+
+    /// Parses the same lines as a single race, ignoring the spaces
+    /// between the numbers.
+    pub fn parse_kerned(input: &str) -> BoatRace {
+        BoatRace::try_parse_kerned(input).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like `parse_kerned`, but gives back an error for bad input.
+    pub fn try_parse_kerned(input: &str) -> Result<BoatRace, String> {
+        let lines = shared::input_as_lines(input);
+        let num = |s: &str| {
+            s.split_whitespace()
+                .skip(1)
+                .flat_map(|x| x.chars())
+                .try_fold(0i64, |n, c| {
+                    let d = c.to_digit(10).ok_or("Unable to parse number")?;
+                    n.checked_mul(10).and_then(|n| n.checked_add(d.into())).ok_or("Number is too big")
+                })
+        };
+        let [time, dist, ..] = lines[..] else {
+            return Err("Expected time and distance lines".to_string());
+        };
+
+        Ok(BoatRace {
+            time: num(time)?,
+            dist: num(dist)?,
+        })
+    }
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    let races = BoatRace::parse_races(input);
+
+    let result: i64 = 
+        races.iter()
+        .map(|x| x.find_wins())
+        .product();
+
+    result.to_string()
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    let race = BoatRace::parse_kerned(input);
+
+    race.find_wins().to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn find_wins_beats_record(time in 1..2000i64, hold in 0..1000i64) {
+            let hold = hold % (time / 2 + 1);
+            let race = BoatRace { time, dist: hold * (time - hold) };
+
+            prop_assert_eq!(race.find_wins(), (time - 2 * hold - 1).max(0));
+        }
+
+        #[test]
+        fn find_wins_matches_walk(time in -5..300i64, dist in -5..30000i64) {
+            let race = BoatRace { time, dist };
+            let walked = (1..time).filter(|i| i * (time - i) > dist).count() as i64;
+
+            prop_assert_eq!(race.find_wins(), walked);
+        }
+
+        #[test]
+        fn parse_rejects(input in "Time: 1|Time: x\nDistance: 1|Time: 1\nDistance: 1[0-9]{19}") {
+            prop_assert!(BoatRace::try_parse_kerned(&input).is_err());
+        }
+    }
+
+    #[test]
+    fn solve_sample() {
+        let races = [
+            BoatRace { time: 7, dist: 9, },
+            BoatRace { time: 15, dist: 40, },
+            BoatRace { time: 30, dist: 200, },
+        ];
+
+        assert_eq!(races.len(), 3);
+        assert_eq!(races[0].time, 7);
+        assert_eq!(races[0].dist, 9);
+
+        assert_eq!(races[0].find_wins(), 4);
+        assert_eq!(races[1].find_wins(), 8);
+        assert_eq!(races[2].find_wins(), 9);
+    }
+
+    #[test]
+    fn parse_sample() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+
+        let races = BoatRace::parse_races(input);
+        assert_eq!(races.len(), 3);
+        assert_eq!(races[2].time, 30);
+        assert_eq!(races[2].dist, 200);
+
+        let race = BoatRace::parse_kerned(input);
+        assert_eq!(race.time, 71530);
+        assert_eq!(race.dist, 940200);
+        assert_eq!(race.find_wins(), 71503);
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "503424");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "32607562");
+    }
+}
@@ -0,0 +1,365 @@
+//! Day 7: Camel Cards
+
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+/// Kind of hand, weakest first.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HandType {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    FullHouse = 4,
+    FourOfAKind = 5,
+    FiveOfAKind = 6,
+}
+
+/// A hand of five cards and its bid. Cards are stored by value, from 2
+/// up to 14 for an ace.
+#[derive(Debug)]
+pub struct Hand {
+    pub bid: u64,
+    pub htype: HandType,
+    pub cards: [u32; 5],
+}
+
+impl Hand {
+    /// Sort key ranking hands by type, then card by card.
+    pub fn strength(&self) -> u64 {
+        10000000000 * self.htype as u64 +
+        100000000 * self.cards[0] as u64 +
+        1000000 * self.cards[1] as u64 +
+        10000 * self.cards[2] as u64 +
+        100 * self.cards[3] as u64 +
+        self.cards[4] as u64
+    }
+
+    /// Value of a card label, e.g. 'T' is 10.
+    pub fn card_value(c: char) -> u32 {
+        match c {
+            'A' => 14,
+            'K' => 13,
+            'Q' => 12,
+            'J' => 11,
+            'T' => 10,
+            _ => c.to_digit(10).unwrap_or(0),
+        }
+    }
+
+    /// Label for a card value; the inverse of [`Hand::card_value`].
+    pub fn card_char(v: u32) -> char {
+        match v {
+            14 => 'A',
+            13 => 'K',
+            12 => 'Q',
+            11 => 'J',
+            10 => 'T',
+            _ => char::from_digit(v, 10).unwrap_or('?'),
+        }
+    }
+
+    /// Counts of each card value as `(value, count)`, largest count
+    /// first, then highest value.
+    pub fn parse_buckets(cards: &str) -> Vec<(u32, u32)> {
+        let mut res: [u32; 15] = [0; 15];
+    
+        for ch in cards.chars() {
+            res[Hand::card_value(ch) as usize] += 1;
+        }
+    
+        let mut vres =
+            res.iter()
+            .enumerate()
+            .filter(|&(i,n)| i > 0 && *n > 0)
+            .map(|(i,n)| (i as u32, *n))
+            .collect::<Vec<_>>();
+    
+        vres.sort_by(|a, b| {
+            if b.1 == a.1 {
+                b.0.cmp(&a.0)
+            } else {
+                b.1.cmp(&a.1)
+            }
+        });
+        vres
+    }
+
+    // Bids are kept to a u32 so the winnings fit in a u64
+    fn parse_bid(bid: &str) -> Result<u64, String> {
+        bid.parse::<u32>().map(u64::from).map_err(|_| "Unable to parse bid".to_string())
+    }
+
+    /// Kind of hand made by five card labels.
+    pub fn parse_type(cards: &str) -> HandType {
+        // Counts as in `parse_buckets`, largest first, without the Vec
+        let mut counts: [u32; 15] = [0; 15];
+        for ch in cards.chars() {
+            counts[Hand::card_value(ch) as usize] += 1;
+        }
+        counts[0] = 0;
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match counts[0] {
+            5 => HandType::FiveOfAKind,
+            4 => HandType::FourOfAKind,
+            3 => match counts[1] {
+                2 => HandType::FullHouse,
+                _ => HandType::ThreeOfAKind,
+            },
+            2 => match counts[1] {
+                2 => HandType::TwoPair,
+                _ => HandType::OnePair,
+            },
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cards = self.cards.iter().map(|c| Hand::card_char(*c)).collect::<String>();
+        write!(f, "{} {}", cards, self.bid)
+    }
+}
+
+impl From<&str> for Hand {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = String;
+
+    /// Parses a line like `32T3K 765`: five card labels, then the bid.
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let (cards, bid) = item.split_once(' ').ok_or("Hand needs cards and a bid")?;
+        let sp_bid = Hand::parse_bid(bid)?;
+        if !cards.chars().all(|c| "23456789TJQKA".contains(c)) {
+            return Err(format!("Unknown card in {cards}"));
+        }
+        let sp_htype = Hand::parse_type(cards);
+
+        let values = cards.chars().map(Hand::card_value).collect::<Vec<_>>();
+        let sp_cards: [u32; 5] = values.try_into().map_err(|_| "Hand needs five cards")?;
+
+        Ok(Hand {
+            bid: sp_bid,
+            htype: sp_htype,
+            cards: sp_cards,
+        })
+    }
+}
+
+/// Sum of each hand's bid times its rank.
+pub fn total_winnings(lines: &[&str]) -> u64 {
+    rank_winnings(lines.iter().map(|x| Hand::from(*x)).collect())
+}
+
+/// Sum of each hand's bid times its rank among `hands`.
+pub fn rank_winnings(mut hands: Vec<Hand>) -> u64 {
+    hands.sort_by(|a,b| {
+        let sb = b.strength();
+        let sa = a.strength();
+        sb.cmp(&sa)
+    });
+
+    //for hand in &hands {
+    //    println!("{:?} = {:?}", hand.cards, hand.bid);
+    //}
+
+    let hlen = hands.len() as u64;
+
+    hands.iter()
+        .enumerate()
+        .map(|(i, x)| (hlen - (i as u64)) * x.bid)
+        .sum::<u64>()
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    solve_part1_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part1`, but reads the input a line at a time. Ranking needs
+/// every hand, so those are kept, but not the lines they came from.
+pub fn solve_part1_from<R: BufRead>(input: R) -> io::Result<String> {
+    let hands = shared::stream::fold_parsed(input, vec![], |mut hands, hand: Hand| {
+        hands.push(hand);
+        hands
+    })?;
+    Ok(rank_winnings(hands).to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    let _lines = shared::input_as_lines(input);
+
+    "".to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn arb_hand() -> impl Strategy<Value = Hand> {
+        ("[2-9TJQKA]{5}", 1..1000u64)
+            .prop_map(|(cards, bid)| Hand::from(format!("{cards} {bid}").as_str()))
+    }
+
+    proptest! {
+        #[test]
+        fn parse_hand_roundtrip(hand in arb_hand()) {
+            let parsed = Hand::from(hand.to_string().as_str());
+
+            prop_assert_eq!(parsed.cards, hand.cards);
+            prop_assert_eq!(parsed.htype, hand.htype);
+            prop_assert_eq!(parsed.bid, hand.bid);
+        }
+
+        #[test]
+        fn parse_hand_rejects(line in "[2-9TJQKA]{4,6} 1[0-9]{10}|[2-9TJQKA]{6} 1|[2-9TJQKA]{0,4} 1|[2-9]{4}[1Xa] 1|[2-9]{5}") {
+            prop_assert!(line.parse::<Hand>().is_err());
+        }
+
+        #[test]
+        fn hand_ordering_total(a in arb_hand(), b in arb_hand()) {
+            let by_rules = (a.htype as u64, a.cards).cmp(&(b.htype as u64, b.cards));
+
+            prop_assert_eq!(a.strength().cmp(&b.strength()), by_rules);
+            prop_assert_eq!(b.strength().cmp(&a.strength()), by_rules.reverse());
+        }
+
+        #[test]
+        fn hand_type_ignores_order(cards in Just(vec!['2', '7', '7', 'K', 'A']).prop_shuffle()) {
+            let cards = cards.into_iter().collect::<String>();
+            prop_assert_eq!(Hand::parse_type(&cards), HandType::OnePair);
+        }
+    }
+
+    #[test]
+    fn parse_buckets() {
+        assert_eq!(Hand::parse_buckets("AAAAA"),
+            vec![(14, 5)]);
+        assert_eq!(Hand::parse_buckets("1KKKK"),
+            vec![(13, 4), (1, 1)]);
+        assert_eq!(Hand::parse_buckets("11221"),
+            vec![(1, 3), (2, 2)]);
+        assert_eq!(Hand::parse_buckets("22111"),
+            vec![(1, 3), (2, 2)]);
+        assert_eq!(Hand::parse_buckets("11231"),
+            vec![(1, 3), (3, 1), (2, 1)]);
+        assert_eq!(Hand::parse_buckets("42QQ2"),
+            vec![(12, 2), (2, 2), (4, 1)]);
+        assert_eq!(Hand::parse_buckets("AKT33"),
+            vec![(3, 2), (14, 1), (13, 1), (10, 1)]);
+        assert_eq!(Hand::parse_buckets("23857"),
+            vec![(8, 1), (7, 1), (5, 1), (3, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn parse_type() {
+        assert_eq!(Hand::parse_type("AAAAA"),
+            HandType::FiveOfAKind);
+        assert_eq!(Hand::parse_type("1KKKK"),
+            HandType::FourOfAKind);
+        assert_eq!(Hand::parse_type("11221"),
+            HandType::FullHouse);
+        assert_eq!(Hand::parse_type("11231"),
+            HandType::ThreeOfAKind);
+        assert_eq!(Hand::parse_type("42QQ2"),
+            HandType::TwoPair);
+        assert_eq!(Hand::parse_type("AKT33"),
+            HandType::OnePair);
+        assert_eq!(Hand::parse_type("23857"),
+            HandType::HighCard);
+    }
+
+    #[test]
+    fn parse_hand() {
+        let h1 = Hand::from("AAAAA 123");
+        assert_eq!(h1.cards, [14,14,14,14,14]);
+        assert_eq!(h1.htype, HandType::FiveOfAKind);
+        assert_eq!(h1.bid, 123);
+        assert_eq!(h1.strength(), 61414141414);
+
+        let h2 = Hand::from("55QQK 99");
+        assert_eq!(h2.cards, [5,5,12,12,13]);
+        assert_eq!(h2.htype, HandType::TwoPair);
+        assert_eq!(h2.bid, 99);
+        assert_eq!(h2.strength(), 20505121213);
+    }
+
+    #[test]
+    fn parse_sample() {
+        let input = include_str!("../input2.txt");
+        let lines = shared::input_as_lines(input);
+    
+        let mut hands: Vec<Hand> = 
+            lines.iter()
+                .map(|x| Hand::from(*x))
+                .collect::<Vec<_>>();
+
+        hands.sort_by(|a,b| {
+            let sb = b.strength();
+            let sa = a.strength();
+            sb.cmp(&sa)
+        });
+
+        assert_eq!(hands.len(), 5);
+        assert_eq!(hands[0].strength(), 31212121114);
+        assert_eq!(hands[1].strength(), 31005051105);
+        assert_eq!(hands[2].strength(), 21313060707);
+        assert_eq!(hands[3].strength(), 21310111110);
+        assert_eq!(hands[4].strength(), 10302100313);
+
+        assert_eq!(total_winnings(&lines), 6440);
+    }
+
+    // Each hand with its type and strength, weakest first, and what its
+    // rank wins
+    fn render_ranking(input: &str) -> String {
+        let mut hands = shared::input_as_lines(input).into_iter().map(Hand::from).collect::<Vec<_>>();
+        hands.sort_by_key(|h| h.strength());
+
+        hands.iter()
+            .enumerate()
+            .map(|(i, h)| format!("{:>4}. {:<10} {:<12} {:>14} {:>8}\n",
+                i + 1, h.to_string(), format!("{:?}", h.htype), h.strength(), (i as u64 + 1) * h.bid))
+            .collect()
+    }
+
+    #[test]
+    fn snapshot_samples() {
+        insta::assert_snapshot!("ranking_input2", render_ranking(include_str!("../input2.txt")));
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "251216224");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "zz");
+    }
+}
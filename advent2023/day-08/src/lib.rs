@@ -0,0 +1,406 @@
+//! Day 8: Haunted Wasteland
+
+use std::fmt;
+use std::str::FromStr;
+use rustc_hash::FxHashMap;
+use shared::cancel::{Cancel, Cancelled};
+use shared::parse::AocParse;
+
+// Steps walked between checks for cancellation
+const CHECK_EVERY: u64 = 1 << 16;
+
+/// A node's index in the network.
+pub type NodeId = u32;
+
+/// Left/right instructions and the map of nodes. Nodes are numbered in
+/// the order they are listed, and their neighbours are kept by number.
+#[derive(Debug)]
+pub struct CamelNetwork {
+    pub inst: String,
+    /// Node names, by id.
+    pub names: Vec<String>,
+    /// Each node's `(left, right)` neighbours, by id.
+    pub exits: Vec<(NodeId, NodeId)>,
+}
+
+// One line of the map, borrowed from the input
+#[derive(AocParse)]
+#[aoc("{name} = ({left}, {right})")]
+struct Node<'a> {
+    name: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+
+impl CamelNetwork {
+    /// The id of the node called `name`.
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names.iter().position(|n| n == name).map(|i| i as NodeId)
+    }
+
+    /// The names of the `(left, right)` neighbours of the node called `name`.
+    pub fn node(&self, name: &str) -> Option<(&str, &str)> {
+        let (left, right) = self.exits[self.id(name)? as usize];
+        Some((&self.names[left as usize], &self.names[right as usize]))
+    }
+
+    /// Every node's name and the names of its `(left, right)` neighbours.
+    pub fn nodes(&self) -> impl Iterator<Item = (&str, (&str, &str))> {
+        self.names.iter()
+            .zip(&self.exits)
+            .map(|(n, (l, r))| (n.as_str(), (self.names[*l as usize].as_str(), self.names[*r as usize].as_str())))
+    }
+
+    // The node `inst` leads to from `id`
+    fn step(&self, id: NodeId, inst: u8) -> NodeId {
+        match inst {
+            b'L' => self.exits[id as usize].0,
+            b'R' => self.exits[id as usize].1,
+            _ => panic!("Invalid instruction"),
+        }
+    }
+
+    /// Steps taken to walk from `AAA` to `ZZZ`, or None if it can not be
+    /// reached.
+    pub fn find_steps_pt1(&self) -> Option<u32> {
+        self.find_steps_pt1_with(&Cancel::default()).unwrap()
+    }
+
+    /// Like `find_steps_pt1`, but stops once `cancel` is cancelled, since
+    /// the walk can take as many steps as there are nodes times
+    /// instructions before it is known `ZZZ` can not be reached.
+    pub fn find_steps_pt1_with(&self, cancel: &Cancel) -> Result<Option<u32>, Cancelled> {
+        let mut curr = self.id("AAA").expect("no node AAA");
+        let Some(end) = self.id("ZZZ") else {
+            return Ok(None);
+        };
+
+        // Past this many steps the walk has been in some node at the same
+        // instruction before, so it only goes round a loop
+        let limit = self.names.len() as u64 * self.inst.len() as u64;
+        let mut count = 0u64;
+
+        for inst in self.inst.bytes().cycle() {
+            curr = self.step(curr, inst);
+            count += 1;
+            if curr == end {
+                return Ok(u32::try_from(count).ok());
+            }
+            if count >= limit {
+                break;
+            }
+            if count.is_multiple_of(CHECK_EVERY) {
+                cancel.check()?;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Steps until every ghost, starting on each node ending in `A`, is
+    /// on a node ending in `Z` at once, or 0 if there are no ghosts, one
+    /// never reaches a `Z` node, or it would take more steps than fit in
+    /// a u64.
+    pub fn find_steps_pt2(&self) -> u64 {
+        let mut count = 0u64;
+
+        let ends = self.names.iter().map(|n| n.ends_with('Z')).collect::<Vec<_>>();
+        let mut ghosts =
+            self.names.iter()
+            .enumerate()
+            .filter(|(_, n)| n.ends_with('A'))
+            .map(|(i, _)| i as NodeId)
+            .collect::<Vec<_>>();
+        // Steps each ghost took to first reach a Z node
+        let mut firsts: Vec<Option<u64>> = vec![None; ghosts.len()];
+
+        // Past this many steps every ghost is going round a loop it has
+        // already been round once
+        let limit = self.names.len() as u64 * self.inst.len() as u64;
+
+        if ghosts.is_empty() {
+            return 0;
+        }
+        for inst in self.inst.bytes().cycle() {
+            count += 1;
+            if count > limit {
+                break;
+            }
+
+            for (g, first) in ghosts.iter_mut().zip(firsts.iter_mut()) {
+                *g = self.step(*g, inst);
+
+                if ends[*g as usize] && first.is_none() {
+                    *first = Some(count);
+                }
+            }
+
+            if let Some(counts) = firsts.iter().copied().collect::<Option<Vec<_>>>() {
+                return shared::checked_lcm(&counts).unwrap_or(0);
+            }
+        }
+        0
+    }
+}
+
+impl fmt::Display for CamelNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n\n", self.inst)?;
+
+        let mut nodes = self.nodes().collect::<Vec<_>>();
+        nodes.sort();
+
+        for (k, (left, right)) in nodes {
+            writeln!(f, "{k} = ({left}, {right})")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for CamelNetwork {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl FromStr for CamelNetwork {
+    type Err = String;
+
+    /// Parses the instructions line, then one node per line. Every
+    /// neighbour must be a node listed too.
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let inst = item.lines().next().unwrap_or_default().to_string();
+        if inst.is_empty() || !inst.bytes().all(|b| b == b'L' || b == b'R') {
+            return Err("Instructions must be a line of L and R".to_string());
+        }
+
+        let nodes = item.lines()
+            .skip(1)
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| Node::try_from(l).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Names are only hashed here, to number the neighbours
+        let ids = nodes.iter()
+            .enumerate()
+            .map(|(i, n)| (n.name, i as NodeId))
+            .collect::<FxHashMap<_, _>>();
+        let id = |name: &str| ids.get(name).copied().ok_or_else(|| format!("no node {name}"));
+        let exits = nodes.iter()
+            .map(|n| Ok((id(n.left)?, id(n.right)?)))
+            .collect::<Result<_, String>>()?;
+
+        Ok(CamelNetwork {
+            inst,
+            names: nodes.iter().map(|n| n.name.to_string()).collect(),
+            exits,
+        })
+    }
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    solve_part1_with(input, &Cancel::default()).unwrap()
+}
+
+/// Like `solve_part1`, but stops once `cancel` is cancelled.
+pub fn solve_part1_with(input: &str, cancel: &Cancel) -> Result<String, Cancelled> {
+    let net = CamelNetwork::from(input);
+    Ok(net.find_steps_pt1_with(cancel)?.unwrap_or(0).to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    let net = CamelNetwork::from(input);
+    net.find_steps_pt2().to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use proptest::prelude::*;
+
+    fn arb_network() -> impl Strategy<Value = CamelNetwork> {
+        prop::collection::hash_set("[0-9A-Z]{3}", 1..30)
+            .prop_flat_map(|names| {
+                let n = names.len() as NodeId;
+                ("[LR]{1,20}", Just(names.into_iter().collect::<Vec<_>>()), prop::collection::vec((0..n, 0..n), n as usize))
+            })
+            .prop_map(|(inst, names, exits)| CamelNetwork { inst, names, exits })
+    }
+
+    // Each node's name and its neighbours', whatever the ids
+    fn node_map(net: &CamelNetwork) -> HashMap<&str, (&str, &str)> {
+        net.nodes().collect()
+    }
+
+    // A straight path AAA -> 001 -> 002 -> ... -> ZZZ, where both
+    // exits of every node lead to the next one.
+    fn chain_network(inst: String, len: usize) -> CamelNetwork {
+        let names = (0..=len)
+            .map(|i| match i {
+                0 => "AAA".to_string(),
+                _ if i == len => "ZZZ".to_string(),
+                _ => format!("{i:03}"),
+            })
+            .collect::<Vec<_>>();
+
+        let exits = (0..=len)
+            .map(|i| (i + 1).min(len) as NodeId)
+            .map(|next| (next, next))
+            .collect();
+
+        CamelNetwork { inst, names, exits }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_network_roundtrip(net in arb_network()) {
+            let parsed = CamelNetwork::from(net.to_string().as_str());
+
+            prop_assert_eq!(&parsed.inst, &net.inst);
+            prop_assert_eq!(node_map(&parsed), node_map(&net));
+        }
+
+        #[test]
+        fn steps_pt1_along_chain(inst in "[LR]{1,20}", len in 1usize..200) {
+            let net = chain_network(inst, len);
+            prop_assert_eq!(net.find_steps_pt1(), Some(len as u32));
+        }
+    }
+
+    #[test]
+    fn solve_sample() {
+        let input = include_str!("../input2.txt");
+
+        let net = CamelNetwork::from(input);
+
+        assert_eq!(net.inst, String::from("RL"));
+
+        assert_eq!(net.names.len(), 7);
+        assert_eq!(net.node("AAA"), Some(("BBB", "CCC")));
+        assert_eq!(net.node("BBB"), Some(("DDD", "EEE")));
+        assert_eq!(net.exits[0], (1, 2));
+    }
+
+    #[test]
+    fn solve_steps_pt1() {
+        let input2 = include_str!("../input2.txt");
+        let net2 = CamelNetwork::from(input2);
+        assert_eq!(net2.find_steps_pt1(), Some(2));
+
+        let input3 = include_str!("../input3.txt");
+        let net3 = CamelNetwork::from(input3);
+        assert_eq!(net3.find_steps_pt1(), Some(6));
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let looping = CamelNetwork::from("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(looping.find_steps_pt1(), None);
+        assert_eq!(CamelNetwork::from("L\n\nAAA = (AAA, AAA)").find_steps_pt1(), None);
+        assert_eq!(super::solve_part1("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)"), "0");
+    }
+
+    #[test]
+    fn cancel_long_walk() {
+        let net = chain_network("L".to_string(), 200_000);
+        let cancel = Cancel::default();
+
+        cancel.cancel();
+        assert_eq!(net.find_steps_pt1_with(&cancel), Err(Cancelled));
+        assert_eq!(solve_part1_with(include_str!("../input3.txt"), &cancel), Ok("6".to_string()));
+    }
+
+    #[test]
+    fn solve_steps_pt2() {
+        let input4 = include_str!("../input4.txt");
+        let net4 = CamelNetwork::from(input4);
+        assert_eq!(net4.find_steps_pt2(), 6);
+
+        let stuck = CamelNetwork::from("LR\n\n11A = (11B, 11B)\n11B = (11A, 11A)\n22Z = (22Z, 22Z)");
+        assert_eq!(stuck.find_steps_pt2(), 0);
+        assert_eq!(CamelNetwork::from("L\n\nBBB = (BBB, BBB)").find_steps_pt2(), 0);
+
+        // 11A's ghost reaches a Z node every step, but 22A's never does
+        let one_stuck = CamelNetwork::from("LR\n\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)\n22A = (22B, 22B)\n22B = (22A, 22A)");
+        assert_eq!(one_stuck.find_steps_pt2(), 0);
+    }
+
+    #[test]
+    fn parse_rejects() {
+        for bad in ["", "LRX\n\nAAA = (AAA, AAA)", "L\n\nAAA = (BBB, AAA)", "L\n\nAAA = BBB"] {
+            assert!(bad.parse::<CamelNetwork>().is_err(), "{bad:?}");
+        }
+    }
+
+    // The parsed network, then the walk from AAA if there is one, and the
+    // steps each ghost takes to first reach a Z node
+    fn render_walks(input: &str) -> String {
+        let net = CamelNetwork::from(input);
+        let step = |node: &str, inst: char| match inst {
+            'L' => net.node(node).unwrap().0,
+            _ => net.node(node).unwrap().1,
+        };
+        let mut out = format!("{net}\n");
+
+        if net.id("AAA").is_some() {
+            let mut walk = vec!["AAA"];
+            for inst in net.inst.chars().cycle().take(1000) {
+                walk.push(step(walk.last().unwrap(), inst));
+                if walk.last() == Some(&"ZZZ") {
+                    break;
+                }
+            }
+            out += &format!("walk: {}\n", walk.join(" -> "));
+        }
+
+        let mut ghosts = net.names.iter().filter(|k| k.ends_with('A')).map(|k| k.as_str()).collect::<Vec<_>>();
+        ghosts.sort();
+        for start in ghosts {
+            let (mut node, mut steps) = (start, 0);
+            for inst in net.inst.chars().cycle().take(1000) {
+                node = step(node, inst);
+                steps += 1;
+                if node.ends_with('Z') {
+                    break;
+                }
+            }
+            out += &format!("ghost {start}: {node} after {steps}\n");
+        }
+        out
+    }
+
+    #[test]
+    fn snapshot_samples() {
+        insta::assert_snapshot!("walks_input2", render_walks(include_str!("../input2.txt")));
+        insta::assert_snapshot!("walks_input3", render_walks(include_str!("../input3.txt")));
+        insta::assert_snapshot!("walks_input4", render_walks(include_str!("../input4.txt")));
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "18827");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "20220305520997");
+    }
+}
@@ -0,0 +1,247 @@
+//! Day 9: Mirage Maintenance
+
+use std::fmt;
+use std::io::{self, BufRead};
+use itertools::Itertools;
+use shared::parse::AocParse;
+
+/// The recorded values of one OASIS reading.
+#[derive(AocParse)]
+#[aoc("{input}")]
+pub struct History {
+    pub input: Vec<i64>,
+}
+
+impl History {
+    /// Differences between each pair of neighbouring values.
+    pub fn next_row(src: &[i64]) -> Vec<i64> {
+        src.iter()
+           .tuple_windows::<(_, _)>()
+           .map(|x| x.1 - x.0)
+           .collect()
+    }
+    
+    /// Extrapolates the next value in the sequence.
+    pub fn next_seq(&self) -> i64 {
+        self.try_next_seq().expect("next value overflows an i64")
+    }
+
+    /// Like `next_seq`, but `None` if the differences or the next value
+    /// overflow an i64.
+    pub fn try_next_seq(&self) -> Option<i64> {
+        let mut v: Vec<Vec<i64>> = vec![self.input.clone()];
+
+        loop {
+            let w = v.last().unwrap();
+            let x = w.iter()
+                .tuple_windows::<(_, _)>()
+                .map(|x| x.1.checked_sub(*x.0))
+                .collect::<Option<Vec<_>>>()?;
+
+            if x.iter().all(|a| *a == 0) {
+                return v.iter()
+                    .filter_map(|a| a.last())
+                    .try_fold(0i64, |n, a| n.checked_add(*a));
+            }
+
+            v.push(x);
+        }
+    }
+
+    /// The same history backwards, for extrapolating the previous value.
+    pub fn reverse(&self) -> History {
+        let input =
+            self.input
+                .iter()
+                .rev()
+                .cloned()
+                .collect();
+
+        History {
+            input
+        }
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.input.iter().join(" "))
+    }
+}
+
+impl From<&str> for History {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+const TOO_BIG: &str = "the answer overflows an i64";
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    solve_part1_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part1`, but reads the input a line at a time.
+pub fn solve_part1_from<R: BufRead>(input: R) -> io::Result<String> {
+    shared::stream::try_fold_parsed(input, 0i64, |total, h: History| {
+        h.try_next_seq().and_then(|n| total.checked_add(n)).ok_or(TOO_BIG)
+    })
+    .map(|n| n.to_string())
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    solve_part2_from(input.as_bytes()).unwrap()
+}
+
+/// Like `solve_part2`, but reads the input a line at a time.
+pub fn solve_part2_from<R: BufRead>(input: R) -> io::Result<String> {
+    shared::stream::try_fold_parsed(input, 0i64, |total, h: History| {
+        h.reverse().try_next_seq().and_then(|n| total.checked_add(n)).ok_or(TOO_BIG)
+    })
+    .map(|n| n.to_string())
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn too_big_rejected(big in i64::MAX / 2..i64::MAX) {
+            let input = format!("0 {big} 0\n");
+            prop_assert!(solve_part1_from(input.as_bytes()).is_err());
+            prop_assert!(solve_part2_from(input.as_bytes()).is_err());
+        }
+
+        #[test]
+        fn parse_history_roundtrip(input in prop::collection::vec(-1_000_000..1_000_000i64, 1..25)) {
+            let h = History { input };
+            prop_assert_eq!(History::from(h.to_string().as_str()).input, h.input);
+        }
+
+        #[test]
+        fn reverse_twice_identity(input in prop::collection::vec(any::<i64>(), 0..25)) {
+            let h = History { input };
+            prop_assert_eq!(h.reverse().reverse().input, h.input);
+        }
+
+        #[test]
+        fn next_seq_quadratic(a in -1000..1000i64, b in -1000..1000i64, c in -1000..1000i64, len in 4..20i64) {
+            let f = |i: i64| a + b * i + c * i * i;
+            let h = History { input: (0..len).map(f).collect() };
+
+            prop_assert_eq!(h.next_seq(), f(len));
+            prop_assert_eq!(h.reverse().next_seq(), f(-1));
+        }
+    }
+
+    #[test]
+    fn parse_input() {
+        let h = History::from("0 3 6 9 12 15");
+
+        assert_eq!(h.input.len(), 6);
+        assert_eq!(h.input, vec![0, 3, 6, 9, 12, 15]);
+    }
+
+    #[test]
+    fn solve_next_row() {
+        let h = History::from("0 3 6 9 12 15");
+
+        let r1 = History::next_row(&h.input);
+        assert_eq!(r1, vec![3, 3, 3, 3, 3]);
+
+        let r2 = History::next_row(&r1);
+        assert_eq!(r2, vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn solve_next_seq() {
+        let h1 = History::from("0 3 6 9 12 15");
+        assert_eq!(h1.next_seq(), 18);
+
+        let h2 = History::from("1 3 6 10 15 21");
+        assert_eq!(h2.next_seq(), 28);
+
+        let h3 = History::from("10 13 16 21 30 45");
+        assert_eq!(h3.next_seq(), 68);
+    }
+
+    #[test]
+    fn solve_sum() {
+        let input = include_str!("../input2.txt");
+        let lines = shared::input_as_lines(input);
+    
+        let total =
+            lines.iter()
+            .map(|x| History::from(*x))
+            .map(|h| h.next_seq())
+            .sum::<i64>();
+    
+        assert_eq!(total, 114);
+
+        let total =
+            lines.iter()
+            .map(|x| History::from(*x))
+            .map(|h| h.reverse())
+            .map(|h| h.next_seq())
+            .sum::<i64>();
+
+        assert_eq!(total, 2); 
+    }
+
+    // Each history's pyramid of differences, down to the row of zeros,
+    // with the values extrapolated either side of the top row
+    fn render_pyramid(input: &str) -> String {
+        let mut out = String::new();
+        for line in shared::input_as_lines(input) {
+            let h = History::from(line);
+            out += &format!("{} [{}] {}\n", h.reverse().next_seq(), h, h.next_seq());
+
+            let mut row = h.input.clone();
+            for depth in 0.. {
+                let cells = row.iter().map(|x| format!("{x:>4}")).collect::<String>();
+                out += &format!("{}{cells}\n", "  ".repeat(depth));
+                if row.iter().all(|x| *x == 0) {
+                    break;
+                }
+                row = History::next_row(&row);
+            }
+            out += "\n";
+        }
+        out
+    }
+
+    #[test]
+    fn snapshot_samples() {
+        insta::assert_snapshot!("pyramid_input2", render_pyramid(include_str!("../input2.txt")));
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "1479011877");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "973");
+    }
+}
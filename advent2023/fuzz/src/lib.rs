@@ -0,0 +1,252 @@
+//! Feeds random and mutated puzzle inputs to every day's parser and
+//! solvers. An input the parser rejects with an error is fine; any panic,
+//! arithmetic overflow or hang counts as a crash.
+
+pub mod targets;
+
+use std::any::Any;
+use std::error::Error;
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
+use std::time::Duration;
+use arbitrary::{Arbitrary, Unstructured};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Runs one input, giving back an error if it is rejected as bad input.
+pub type Run = fn(&str) -> Result<(), Box<dyn Error>>;
+
+/// Something to fuzz, with valid inputs to start mutating from.
+pub struct Target {
+    pub name: &'static str,
+    pub run: Run,
+    pub seeds: fn(u64) -> Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Crash {
+    /// A panic, which includes overflow since tests build with overflow
+    /// checks on.
+    Panic(String),
+    /// Still running when the timeout expired.
+    Hang,
+}
+
+const CASE_THREAD: &str = "fuzz-case";
+
+// Crashes are expected, so keep their panic messages off stderr
+fn quiet_panics() {
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(CASE_THREAD) {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "non-string panic".to_string(),
+    }
+}
+
+/// Runs one input on its own thread. Rejecting the input is not a crash.
+/// A thread that hangs is abandoned, since there is no way to stop it.
+pub fn check(run: Run, input: &str, timeout: Duration) -> Result<(), Crash> {
+    quiet_panics();
+
+    let (tx, rx) = mpsc::channel();
+    let input = input.to_string();
+    thread::Builder::new()
+        .name(CASE_THREAD.to_string())
+        .spawn(move || {
+            let result = panic::catch_unwind(|| { let _ = run(&input); });
+            let _ = tx.send(result.map_err(|e| message(&*e)));
+        })
+        .expect("unable to spawn fuzz thread");
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result.map_err(Crash::Panic),
+        Err(RecvTimeoutError::Timeout) => Err(Crash::Hang),
+        Err(RecvTimeoutError::Disconnected) => Err(Crash::Panic("fuzz thread died".to_string())),
+    }
+}
+
+const ALPHABET: &[char] = &[
+    '0', '1', '2', '5', '9', ' ', ' ', '\n', '\n', ':', '|', '-', '=', ',', '(', ')',
+    'L', 'R', 'S', 'J', 'F', '7', '.', 'A', 'K', 'Q', 'T', 'Z',
+];
+
+const EXTREMES: &[&str] = &[
+    "0", "-1", "255", "4294967296", "9223372036854775807", "18446744073709551615",
+    "99999999999999999999999",
+];
+
+/// One fuzz input: either arbitrary text, or one of `seeds` with a few
+/// edits made to it.
+pub fn input(u: &mut Unstructured, seeds: &[String]) -> arbitrary::Result<String> {
+    if seeds.is_empty() || u.ratio(1, 8)? {
+        return match u.arbitrary()? {
+            true => String::arbitrary(u),
+            false => {
+                let len = u.arbitrary_len::<u8>()?;
+                (0..len).map(|_| u.choose(ALPHABET).copied()).collect()
+            }
+        };
+    }
+
+    let mut s = u.choose(seeds)?.clone();
+    for _ in 0..u.int_in_range(1..=4)? {
+        s = mutate(u, &s)?;
+    }
+    Ok(s)
+}
+
+fn mutate(u: &mut Unstructured, s: &str) -> arbitrary::Result<String> {
+    let mut chars = s.chars().collect::<Vec<_>>();
+    let mut lines = s.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+    let pick = |u: &mut Unstructured, len: usize| u.choose_index(len.max(1));
+
+    match u.int_in_range(0..=5)? {
+        0 => {
+            let at = pick(u, chars.len())?.min(chars.len());
+            let len = u.int_in_range(1..=8)?;
+            chars.drain(at..(at + len).min(chars.len()));
+        }
+        1 => {
+            let at = pick(u, chars.len())?.min(chars.len());
+            let len = u.int_in_range(1..=8)?;
+            let extra = (0..len).map(|_| u.choose(ALPHABET).copied()).collect::<arbitrary::Result<Vec<_>>>()?;
+            chars.splice(at..at, extra);
+        }
+        2 if !lines.is_empty() => {
+            let i = pick(u, lines.len())?;
+            lines.insert(i, lines[i].clone());
+            return Ok(lines.join("\n"));
+        }
+        3 if !lines.is_empty() => {
+            lines.remove(pick(u, lines.len())?);
+            return Ok(lines.join("\n"));
+        }
+        4 => {
+            let numbers = number_spans(&chars);
+            if !numbers.is_empty() {
+                let (start, end) = numbers[pick(u, numbers.len())?];
+                let n = match u.arbitrary()? {
+                    true => u.choose(EXTREMES)?.to_string(),
+                    false => i64::arbitrary(u)?.to_string(),
+                };
+                chars.splice(start..end, n.chars());
+            }
+        }
+        _ => chars.truncate(pick(u, chars.len())?),
+    }
+    Ok(chars.into_iter().collect())
+}
+
+fn number_spans(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            spans.push((start, i));
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Runs `iterations` inputs through `target`, returning the first one that
+/// crashes it. The same `seed` always tries the same inputs.
+pub fn fuzz(target: &Target, iterations: usize, seed: u64, timeout: Duration) -> Option<(String, Crash)> {
+    let seeds = (target.seeds)(seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut bytes = vec![0u8; 4096];
+
+    for _ in 0..iterations {
+        rng.fill(&mut bytes[..]);
+        let Ok(input) = input(&mut Unstructured::new(&bytes), &seeds) else { continue };
+
+        if let Err(crash) = check(target.run, &input, timeout) {
+            return Some((input, crash));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(200);
+
+    #[test]
+    fn check_crashes() {
+        assert_eq!(check(|s| { assert!(s.len() < 100); Ok(()) }, "ok", TIMEOUT), Ok(()));
+        assert_eq!(check(|s| { s.parse::<u32>()?; Ok(()) }, "x", TIMEOUT), Ok(()));
+        assert_eq!(check(|s| { s.parse::<u32>().unwrap(); Ok(()) }, "x", TIMEOUT),
+            Err(Crash::Panic("called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }".to_string())));
+        assert_eq!(check(|_| loop { thread::sleep(Duration::from_millis(10)) }, "", TIMEOUT), Err(Crash::Hang));
+    }
+
+    #[test]
+    fn check_overflow() {
+        let crash = check(|s| { std::hint::black_box(s.len() as u8 + 255); Ok(()) }, "x", TIMEOUT);
+        assert_eq!(crash, Err(Crash::Panic("attempt to add with overflow".to_string())));
+    }
+
+    #[test]
+    fn inputs_deterministic() {
+        let seeds = vec!["Card 1: 41 48 | 83 86".to_string(), "0 3 6 9".to_string()];
+        let bytes = (0..=255).cycle().take(2000).collect::<Vec<u8>>();
+
+        let a = input(&mut Unstructured::new(&bytes), &seeds).unwrap();
+        let b = input(&mut Unstructured::new(&bytes), &seeds).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn mutations_edit() {
+        let seed = "Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 30".to_string();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut bytes = vec![0u8; 512];
+
+        let changed = (0..50)
+            .filter(|_| {
+                rng.fill(&mut bytes[..]);
+                mutate(&mut Unstructured::new(&bytes), &seed).unwrap() != seed
+            })
+            .count();
+        assert!(changed > 40);
+    }
+
+    #[test]
+    fn find_planted_crash() {
+        let target = Target {
+            name: "planted",
+            run: |s| { assert!(s.lines().count() < 3, "found it"); Ok(()) },
+            seeds: |_| vec!["Card 1: 1 | 2\nCard 2: 3 | 4".to_string()],
+        };
+
+        let (input, crash) = fuzz(&target, 5000, 0, TIMEOUT).expect("crash not found");
+        assert!(input.lines().count() >= 3);
+        assert_eq!(crash, Crash::Panic("found it".to_string()));
+    }
+
+    #[test]
+    fn number_positions() {
+        let chars = "a 12 b3 456".chars().collect::<Vec<_>>();
+        assert_eq!(number_spans(&chars), vec![(2, 4), (6, 7), (8, 11)]);
+    }
+}
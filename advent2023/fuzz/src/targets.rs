@@ -0,0 +1,161 @@
+use generators::{almanac, calibration, camel_network, cube_games, oasis, pipe_maze, schematic, scratchcards};
+use crate::Target;
+
+/// Every day, run through its fallible parser, then its solvers on any
+/// input that parses. Day 5 part 2 uses the interval solver, since brute
+/// forcing every seed is slow on any input.
+pub static TARGETS: &[Target] = &[
+    Target {
+        name: "day-01",
+        run: |s| {
+            day_01::solve_part1_from(s.as_bytes())?;
+            day_01::solve_part2_from(s.as_bytes())?;
+            Ok(())
+        },
+        seeds: |seed| vec![calibration::generate(seed, 10, 6).input],
+    },
+    Target {
+        name: "day-02",
+        run: |s| {
+            day_02::solve_part1_from(s.as_bytes())?;
+            day_02::solve_part2_from(s.as_bytes())?;
+            Ok(())
+        },
+        seeds: |seed| vec![cube_games::generate(seed, 5, 4).input],
+    },
+    Target {
+        name: "day-03",
+        run: |s| {
+            day_03::Engine::try_parse(&shared::input_as_lines(s))?;
+            day_03::solve_part1(s);
+            day_03::solve_part2(s);
+            Ok(())
+        },
+        seeds: |seed| vec![schematic::generate(seed, 20, 10).input],
+    },
+    Target {
+        name: "day-04",
+        run: |s| {
+            day_04::solve_part1_from(s.as_bytes())?;
+            day_04::solve_part2_from(s.as_bytes())?;
+            Ok(())
+        },
+        seeds: |seed| vec![scratchcards::generate(seed, 6, 5, 8).input],
+    },
+    Target {
+        name: "day-05",
+        run: |s| {
+            day_05::Garden::try_parse(&shared::input_as_lines(s))?;
+            day_05::solve_part1(s);
+            day_05::solve_part2_intervals(s);
+            Ok(())
+        },
+        seeds: |seed| vec![almanac::generate(seed, 3, 4).input],
+    },
+    Target {
+        name: "day-06",
+        run: |s| {
+            for race in day_06::BoatRace::try_parse_races(s)? {
+                race.find_wins();
+            }
+            day_06::BoatRace::try_parse_kerned(s)?.find_wins();
+            Ok(())
+        },
+        seeds: |_| vec!["Time:      7  15   30\nDistance:  9  40  200".to_string()],
+    },
+    Target {
+        name: "day-07",
+        run: |s| {
+            day_07::solve_part1_from(s.as_bytes())?;
+            day_07::solve_part2(s);
+            Ok(())
+        },
+        seeds: |_| vec!["32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483".to_string()],
+    },
+    Target {
+        name: "day-08",
+        run: |s| {
+            let net = s.parse::<day_08::CamelNetwork>()?;
+            net.find_steps_pt2();
+
+            // Part 1 is only asked of networks with an AAA to start from
+            if net.id("AAA").is_some() {
+                net.find_steps_pt1();
+            }
+            Ok(())
+        },
+        seeds: |seed| vec![camel_network::generate(seed, 4, 2, 5).input],
+    },
+    Target {
+        name: "day-09",
+        run: |s| {
+            day_09::solve_part1_from(s.as_bytes())?;
+            day_09::solve_part2_from(s.as_bytes())?;
+            Ok(())
+        },
+        seeds: |seed| vec![oasis::generate(seed, 4, 8, 3).input],
+    },
+    Target {
+        name: "day-10",
+        run: |s| {
+            let maze = s.parse::<day_10::PipeMaze>()?;
+            maze.find_farthest();
+            maze.find_nest();
+            Ok(())
+        },
+        seeds: |seed| vec![pipe_maze::generate(seed, 10, 8, 1).input],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Target> {
+    TARGETS.iter().find(|t| t.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // FUZZ_ITERATIONS and FUZZ_SEED pick how long to run and where to start
+    fn fuzz_target(name: &str) {
+        let env = |var, default| std::env::var(var).ok().and_then(|v| v.parse().ok()).unwrap_or(default);
+        let iterations = env("FUZZ_ITERATIONS", 10_000);
+        let seed = env("FUZZ_SEED", 0);
+
+        let target = find(name).unwrap();
+        if let Some((input, crash)) = crate::fuzz(target, iterations as usize, seed, Duration::from_secs(2)) {
+            panic!("{name} crashed with FUZZ_SEED={seed}: {crash:?}\ninput: {input:?}");
+        }
+    }
+
+    #[test]
+    fn seeds_run_clean() {
+        for t in TARGETS {
+            for input in (t.seeds)(3) {
+                assert_eq!(crate::check(t.run, &input, Duration::from_secs(5)), Ok(()), "{}", t.name);
+                assert!((t.run)(&input).is_ok(), "{} rejected its seed", t.name);
+            }
+        }
+    }
+
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_01() { fuzz_target("day-01"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_02() { fuzz_target("day-02"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_03() { fuzz_target("day-03"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_04() { fuzz_target("day-04"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_05() { fuzz_target("day-05"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_06() { fuzz_target("day-06"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_07() { fuzz_target("day-07"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_08() { fuzz_target("day-08"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_09() { fuzz_target("day-09"); }
+    #[test] #[ignore = "long-running fuzz run"]
+    fn fuzz_day_10() { fuzz_target("day-10"); }
+}
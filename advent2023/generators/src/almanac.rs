@@ -0,0 +1,162 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Puzzle;
+
+const SECTIONS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+const DOMAIN: i64 = 1 << 32;
+
+// Most seeds part 2 is worked out for, by walking every one
+const MAX_WALKED: i64 = 1_000_000;
+
+// (dest, src, len), in the same order as the almanac lines
+type RangeMap = (i64, i64, i64);
+
+/// Day 5 almanac with `seed_pairs` seed ranges and `maps` ranges in each
+/// section. Each section shuffles the whole 32 bit domain around. The
+/// seed ranges are long, so part 2 has no answer; see `generate_with`.
+pub fn generate(seed: u64, seed_pairs: usize, maps: usize) -> Puzzle {
+    generate_with(seed, seed_pairs, maps, DOMAIN / 64)
+}
+
+/// Like `generate`, with seed ranges shorter than `max_len`, so that
+/// walking every seed stays quick. Part 2 is answered by doing just that,
+/// when there are no more than a million seeds.
+pub fn generate_with(seed: u64, seed_pairs: usize, maps: usize, max_len: i64) -> Puzzle {
+    let mut rng = crate::rng(seed);
+
+    let seeds = (0..seed_pairs)
+        .flat_map(|_| {
+            let start = rng.gen_range(0..DOMAIN / 2);
+            let len = rng.gen_range(1..max_len.max(2));
+            [start, len]
+        })
+        .collect::<Vec<_>>();
+
+    let sections = SECTIONS.iter()
+        .map(|_| shuffle_domain(&mut rng, maps.max(1)))
+        .collect::<Vec<_>>();
+
+    let mut rows = vec![format!("seeds: {}", join(&seeds))];
+    for (name, ranges) in SECTIONS.iter().zip(&sections) {
+        rows.push(String::new());
+        rows.push(format!("{name} map:"));
+        rows.extend(ranges.iter().map(|(d, s, l)| format!("{d} {s} {l}")));
+    }
+
+    let location = |s| sections.iter().fold(s, |x, ranges| map_value(x, ranges));
+    let part1 = seeds.iter()
+        .map(|&s| location(s))
+        .min()
+        .unwrap_or(0);
+
+    let walked = seeds.chunks(2).map(|p| p[1]).sum::<i64>();
+    let part2 = (walked <= MAX_WALKED).then(|| {
+        seeds.chunks(2)
+            .flat_map(|p| p[0]..p[0] + p[1])
+            .map(location)
+            .min()
+            .unwrap_or(0)
+    });
+
+    Puzzle {
+        input: rows.join("\n"),
+        part1: Some(part1.to_string()),
+        part2: part2.map(|n| n.to_string()),
+    }
+}
+
+fn join(v: &[i64]) -> String {
+    v.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+// Cut the domain into `pieces` ranges, then lay them back down in a
+// random order.
+fn shuffle_domain(rng: &mut impl Rng, pieces: usize) -> Vec<RangeMap> {
+    let mut cuts = (1..pieces)
+        .map(|_| rng.gen_range(1..DOMAIN))
+        .collect::<Vec<_>>();
+    cuts.extend([0, DOMAIN]);
+    cuts.sort();
+    cuts.dedup();
+
+    let mut ranges = cuts.windows(2)
+        .map(|w| (w[0], w[1] - w[0]))
+        .collect::<Vec<_>>();
+    ranges.shuffle(rng);
+
+    let mut dest = 0;
+    let mut result = ranges.into_iter()
+        .map(|(src, len)| {
+            let r = (dest, src, len);
+            dest += len;
+            r
+        })
+        .collect::<Vec<_>>();
+    result.shuffle(rng);
+    result
+}
+
+fn map_value(x: i64, ranges: &[RangeMap]) -> i64 {
+    ranges.iter()
+        .find(|(_, src, len)| x >= *src && x < src + len)
+        .map_or(x, |(dest, src, _)| x - src + dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 10, 30), generate(7, 10, 30));
+        assert_ne!(generate(7, 10, 30), generate(8, 10, 30));
+    }
+
+    #[test]
+    fn short_seed_ranges() {
+        let puzzle = generate_with(3, 10, 5, 100);
+        let seeds = puzzle.input.lines().next().unwrap()
+            .strip_prefix("seeds: ").unwrap()
+            .split(' ')
+            .map(|x| x.parse::<i64>().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(seeds.len(), 20);
+        assert!(seeds.chunks(2).all(|p| (1..100).contains(&p[1])));
+    }
+
+    #[test]
+    fn sections_cover_domain() {
+        let mut rng = crate::rng(4);
+        let mut ranges = shuffle_domain(&mut rng, 20);
+
+        ranges.sort_by_key(|r| r.1);
+        assert_eq!(ranges.iter().map(|r| r.2).sum::<i64>(), DOMAIN);
+        assert!(ranges.windows(2).all(|w| w[0].1 + w[0].2 == w[1].1));
+    }
+
+    #[test]
+    fn long_seed_ranges_unanswered() {
+        let p = generate(5, 3, 4);
+        assert!(p.part1.is_some());
+        assert_eq!(p.part2, None);
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate_with(seed, 3, 4, 1000);
+            assert_eq!(p.part1, Some(day_05::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_05::solve_part2_intervals(&p.input)), "seed {seed}");
+        }
+    }
+}
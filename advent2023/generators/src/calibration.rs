@@ -0,0 +1,93 @@
+use rand::Rng;
+use crate::Puzzle;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// None of these letters appear in a number word, so filler can never
+// spell out a digit by accident.
+const FILLER: &[u8] = b"abcdjklmpqy";
+
+enum Token {
+    Filler(String),
+    Digit(u32),
+    Word(u32),
+}
+
+/// Day 1 calibration document with `lines` lines of `tokens` tokens each.
+/// Every line holds at least one plain digit.
+pub fn generate(seed: u64, lines: usize, tokens: usize) -> Puzzle {
+    let mut rng = crate::rng(seed);
+    let tokens = tokens.max(1);
+
+    let mut rows = vec![];
+    let mut part1 = 0u64;
+    let mut part2 = 0u64;
+
+    for _ in 0..lines {
+        let forced = rng.gen_range(0..tokens);
+        let line = (0..tokens)
+            .map(|i| match (i == forced, rng.gen_range(0..3)) {
+                (true, _) | (_, 0) => Token::Digit(rng.gen_range(1..=9)),
+                (_, 1) => Token::Word(rng.gen_range(1..=9)),
+                _ => {
+                    let len = rng.gen_range(1..=4);
+                    let s = (0..len)
+                        .map(|_| FILLER[rng.gen_range(0..FILLER.len())] as char)
+                        .collect();
+                    Token::Filler(s)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let digits = line.iter()
+            .filter_map(|t| match t { Token::Digit(d) => Some(*d), _ => None })
+            .collect::<Vec<_>>();
+        let numbers = line.iter()
+            .filter_map(|t| match t { Token::Digit(d) | Token::Word(d) => Some(*d), _ => None })
+            .collect::<Vec<_>>();
+
+        part1 += (digits[0] * 10 + digits[digits.len() - 1]) as u64;
+        part2 += (numbers[0] * 10 + numbers[numbers.len() - 1]) as u64;
+
+        rows.push(line.iter()
+            .map(|t| match t {
+                Token::Filler(s) => s.clone(),
+                Token::Digit(d) => d.to_string(),
+                Token::Word(d) => WORDS[*d as usize - 1].to_string(),
+            })
+            .collect::<String>());
+    }
+
+    Puzzle::solved(rows, part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 50, 6), generate(7, 50, 6));
+        assert_ne!(generate(7, 50, 6), generate(8, 50, 6));
+    }
+
+    #[test]
+    fn every_line_has_a_digit() {
+        let p = generate(1, 200, 3);
+        let lines = p.input.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 200);
+        assert!(lines.iter().all(|l| l.chars().any(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 10, 6);
+            assert_eq!(p.part1, Some(day_01::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_01::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Puzzle;
+
+struct Names {
+    used: HashSet<String>,
+}
+
+impl Names {
+    fn new() -> Self {
+        Names { used: HashSet::from(["AAA".to_string(), "ZZZ".to_string()]) }
+    }
+
+    // A fresh node name, ending in `last` if given, otherwise in anything
+    // but 'A' or 'Z'.
+    fn next(&mut self, rng: &mut impl Rng, last: Option<u8>) -> String {
+        loop {
+            let mut name = (0..3)
+                .map(|_| rng.gen_range(b'A'..=b'Z'))
+                .collect::<Vec<_>>();
+            match last {
+                Some(c) => name[2] = c,
+                None if name[2] == b'A' || name[2] == b'Z' => continue,
+                None => {}
+            }
+
+            let name = String::from_utf8(name).unwrap();
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
+
+/// Day 8 network with `inst_len` instructions and `ghosts` starting
+/// nodes, the first of which is `AAA`. Each ghost reaches its `..Z` node
+/// every p steps, where p is a distinct prime in `period..2 * period`.
+pub fn generate(seed: u64, inst_len: usize, ghosts: usize, period: u64) -> Puzzle {
+    let mut rng = crate::rng(seed);
+    let mut names = Names::new();
+
+    let mut primes = (period.max(2)..period.max(2) * 2)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect::<Vec<_>>();
+    assert!(primes.len() >= ghosts, "not enough primes for {ghosts} ghosts");
+    primes.shuffle(&mut rng);
+    let periods = &primes[..ghosts];
+
+    let inst = (0..inst_len.max(1))
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect::<String>();
+
+    let mut nodes = vec![];
+    for (g, &p) in periods.iter().enumerate() {
+        let (start, end) = match g {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (names.next(&mut rng, Some(b'A')), names.next(&mut rng, Some(b'Z'))),
+        };
+
+        // Every step in the cycle has a left and a right copy of the
+        // node, and either instruction moves one step along.
+        let steps = (1..p)
+            .map(|_| (names.next(&mut rng, None), names.next(&mut rng, None)))
+            .collect::<Vec<_>>();
+
+        let first = steps[0].clone();
+        nodes.push((start, first.clone()));
+        nodes.push((end.clone(), first));
+
+        for (i, (left, right)) in steps.iter().enumerate() {
+            let next = steps.get(i + 1).cloned().unwrap_or((end.clone(), end.clone()));
+            nodes.push((left.clone(), next.clone()));
+            nodes.push((right.clone(), next));
+        }
+    }
+    nodes.shuffle(&mut rng);
+
+    let mut rows = vec![inst, String::new()];
+    rows.extend(nodes.into_iter().map(|(n, (l, r))| format!("{n} = ({l}, {r})")));
+
+    Puzzle::solved(rows, periods[0], periods.iter().product::<u64>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 30, 4, 40), generate(7, 30, 4, 40));
+        assert_ne!(generate(7, 30, 4, 40), generate(8, 30, 4, 40));
+    }
+
+    #[test]
+    fn starts_and_ends() {
+        let p = generate(1, 10, 5, 30);
+        let nodes = p.input.lines()
+            .skip(2)
+            .map(|l| &l[0..3])
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.iter().filter(|n| n.ends_with('A')).count(), 5);
+        assert_eq!(nodes.iter().filter(|n| n.ends_with('Z')).count(), 5);
+        assert!(nodes.contains(&"AAA"));
+        assert!(nodes.contains(&"ZZZ"));
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 4, 2, 5);
+            assert_eq!(p.part1, Some(day_08::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_08::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Puzzle;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+const BAG: [u32; 3] = [12, 13, 14];
+
+/// Day 2 cube game records, `games` games of up to `max_reveals` reveals.
+pub fn generate(seed: u64, games: usize, max_reveals: usize) -> Puzzle {
+    let mut rng = crate::rng(seed);
+
+    let mut rows = vec![];
+    let mut part1 = 0u64;
+    let mut part2 = 0u64;
+
+    for id in 1..=games {
+        let mut max = [0u32; 3];
+        let mut reveals = vec![];
+
+        for _ in 0..rng.gen_range(1..=max_reveals.max(1)) {
+            let mut shown = (0..3)
+                .filter(|_| rng.gen_bool(0.7))
+                .collect::<Vec<_>>();
+            if shown.is_empty() {
+                shown.push(rng.gen_range(0..3));
+            }
+            shown.shuffle(&mut rng);
+
+            let reveal = shown.iter()
+                .map(|&c| {
+                    let n = rng.gen_range(1..=20);
+                    max[c] = max[c].max(n);
+                    format!("{n} {}", COLORS[c])
+                })
+                .collect::<Vec<_>>();
+            reveals.push(reveal.join(", "));
+        }
+
+        if max.iter().zip(BAG).all(|(m, b)| *m <= b) {
+            part1 += id as u64;
+        }
+        part2 += max.iter().map(|&m| m as u64).product::<u64>();
+
+        rows.push(format!("Game {id}: {}", reveals.join("; ")));
+    }
+
+    Puzzle::solved(rows, part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 50, 6), generate(7, 50, 6));
+        assert_ne!(generate(7, 50, 6), generate(8, 50, 6));
+    }
+
+    #[test]
+    fn games_numbered_in_order() {
+        let p = generate(3, 100, 5);
+
+        for (i, line) in p.input.lines().enumerate() {
+            assert!(line.starts_with(&format!("Game {}: ", i + 1)));
+        }
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 5, 4);
+            assert_eq!(p.part1, Some(day_02::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_02::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,33 @@
+pub mod almanac;
+pub mod calibration;
+pub mod camel_network;
+pub mod cube_games;
+pub mod oasis;
+pub mod pipe_maze;
+pub mod schematic;
+pub mod scratchcards;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+/// A generated puzzle input, with the answers to each part where the
+/// generator is able to work them out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Puzzle {
+    fn solved(lines: Vec<String>, part1: impl ToString, part2: impl ToString) -> Self {
+        Puzzle {
+            input: lines.join("\n"),
+            part1: Some(part1.to_string()),
+            part2: Some(part2.to_string()),
+        }
+    }
+}
+
+fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
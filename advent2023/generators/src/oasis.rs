@@ -0,0 +1,68 @@
+use rand::Rng;
+use crate::Puzzle;
+
+/// Day 9 OASIS report of `histories` lines, each `len` values of a
+/// polynomial of at most `degree`.
+pub fn generate(seed: u64, histories: usize, len: usize, degree: usize) -> Puzzle {
+    assert!(len >= degree + 2, "history too short to reach a row of zeroes");
+
+    let mut rng = crate::rng(seed);
+
+    let mut rows = vec![];
+    let mut part1 = 0i64;
+    let mut part2 = 0i64;
+
+    for _ in 0..histories {
+        // Coefficients in the binomial basis are exactly the leading
+        // values of each row of differences.
+        let coeffs = (0..=degree)
+            .map(|_| rng.gen_range(-10..=10))
+            .collect::<Vec<i64>>();
+        let f = |n: i64| -> i64 {
+            coeffs.iter()
+                .enumerate()
+                .map(|(j, c)| c * binomial(n, j as i64))
+                .sum()
+        };
+
+        rows.push((0..len as i64).map(|n| f(n).to_string()).collect::<Vec<_>>().join(" "));
+        part1 += f(len as i64);
+        part2 += f(-1);
+    }
+
+    Puzzle::solved(rows, part1, part2)
+}
+
+// n choose k, extended to negative n
+fn binomial(n: i64, k: i64) -> i64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 20, 21, 6), generate(7, 20, 21, 6));
+        assert_ne!(generate(7, 20, 21, 6), generate(8, 20, 21, 6));
+    }
+
+    #[test]
+    fn binomials() {
+        assert_eq!(binomial(5, 0), 1);
+        assert_eq!(binomial(5, 2), 10);
+        assert_eq!(binomial(5, 6), 0);
+        assert_eq!(binomial(-1, 3), -1);
+        assert_eq!(binomial(-1, 4), 1);
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 4, 8, 3);
+            assert_eq!(p.part1, Some(day_09::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_09::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::{ HashMap, HashSet };
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Puzzle;
+
+const JUNK: &[u8] = b"|-LJF7...";
+
+/// Day 10 pipe maze. The main loop runs around a random shape of up to
+/// `width` by `height` tiles, inside `margin` tiles of junk pipes on
+/// every side. Day 10 assumes the nest is smaller than the area outside
+/// the loop, so keep the margin generous.
+pub fn generate(seed: u64, width: usize, height: usize, margin: usize) -> Puzzle {
+    let mut rng = crate::rng(seed);
+    let (w, h) = (width.max(1) as i64, height.max(2) as i64);
+
+    // Each column of the shape is a single run of tiles overlapping the
+    // one before it, which keeps the outline a simple loop.
+    let mut columns: Vec<(i64, i64)> = vec![];
+    for _ in 0..w {
+        loop {
+            let top = rng.gen_range(0..h);
+            let bottom = rng.gen_range(top..h);
+            let fits = match columns.last() {
+                None => bottom > top,
+                Some(&(t, b)) => top.max(t) <= bottom.min(b),
+            };
+            if fits {
+                columns.push((top, bottom));
+                break;
+            }
+        }
+    }
+
+    let tiles = columns.iter()
+        .enumerate()
+        .flat_map(|(x, &(t, b))| (t..=b).map(move |y| (x as i64, y)))
+        .collect::<HashSet<_>>();
+
+    // Outline edges between lattice points, walked into a loop.
+    let mut links: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
+    for &(x, y) in &tiles {
+        let sides = [
+            ((x, y - 1), (x, y), (x + 1, y)),
+            ((x, y + 1), (x, y + 1), (x + 1, y + 1)),
+            ((x - 1, y), (x, y), (x, y + 1)),
+            ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+        ];
+        for (next, a, b) in sides {
+            if !tiles.contains(&next) {
+                links.entry(a).or_default().push(b);
+                links.entry(b).or_default().push(a);
+            }
+        }
+    }
+
+    let size = (w + 1 + 2 * margin as i64, h + 1 + 2 * margin as i64);
+    let offset = margin as i64;
+    let mut grid = (0..size.1)
+        .map(|_| (0..size.0).map(|_| JUNK[rng.gen_range(0..JUNK.len())]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for (&(x, y), next) in &links {
+        let dirs = next.iter()
+            .map(|&(nx, ny)| (nx - x, ny - y))
+            .collect::<HashSet<_>>();
+        let has = |d: (i64, i64)| dirs.contains(&d);
+
+        grid[(y + offset) as usize][(x + offset) as usize] = match (has((0, -1)), has((0, 1)), has((1, 0)), has((-1, 0))) {
+            (true, true, _, _) => b'|',
+            (_, _, true, true) => b'-',
+            (true, _, true, _) => b'L',
+            (true, _, _, true) => b'J',
+            (_, true, true, _) => b'F',
+            _ => b'7',
+        };
+    }
+
+    // Clear the junk next to the start so its own pipe can be worked out.
+    let mut points = links.keys().cloned().collect::<Vec<_>>();
+    points.sort();
+    let &(sx, sy) = points.choose(&mut rng).unwrap();
+    grid[(sy + offset) as usize][(sx + offset) as usize] = b'S';
+
+    for (dx, dy) in [(0, -1), (0, 1), (1, 0), (-1, 0)] {
+        let (nx, ny) = (sx + dx + offset, sy + dy + offset);
+        let on_grid = nx >= 0 && ny >= 0 && nx < size.0 && ny < size.1;
+        if on_grid && !links.contains_key(&(sx + dx, sy + dy)) {
+            grid[ny as usize][nx as usize] = b'.';
+        }
+    }
+
+    // Pick's theorem: area = inside + boundary / 2 - 1
+    let boundary = links.len() as i64;
+    let inside = tiles.len() as i64 - boundary / 2 + 1;
+
+    let rows = grid.into_iter()
+        .map(|r| String::from_utf8(r).unwrap())
+        .collect();
+
+    Puzzle::solved(rows, boundary / 2, inside)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 20, 20, 10), generate(7, 20, 20, 10));
+        assert_ne!(generate(7, 20, 20, 10), generate(8, 20, 20, 10));
+    }
+
+    #[test]
+    fn single_start() {
+        let p = generate(2, 15, 10, 5);
+        let lines = p.input.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 10 + 1 + 10);
+        assert!(lines.iter().all(|l| l.len() == 15 + 1 + 10));
+        assert_eq!(p.input.matches('S').count(), 1);
+    }
+
+    #[test]
+    fn single_tile_loop() {
+        let p = generate(3, 1, 2, 0);
+
+        assert_eq!(p.part1.as_deref(), Some("3"));
+        assert_eq!(p.part2.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 10, 8, 1);
+            assert_eq!(p.part1, Some(day_10::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_10::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
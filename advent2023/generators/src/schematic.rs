@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use rand::Rng;
+use crate::Puzzle;
+
+const SYMBOLS: &[u8] = b"@#$%&*-+=/";
+
+/// Day 3 engine schematic of `width` by `height` cells. Numbers are at
+/// most three digits long, as in the real puzzle.
+pub fn generate(seed: u64, width: usize, height: usize) -> Puzzle {
+    let mut rng = crate::rng(seed);
+
+    let mut grid: Vec<Vec<u8>> = vec![];
+    for _ in 0..height {
+        let mut row = vec![];
+        while row.len() < width {
+            let room = width - row.len();
+            match rng.gen_range(0..20) {
+                0..=10 => row.push(b'.'),
+                11..=12 => row.push(SYMBOLS[rng.gen_range(0..SYMBOLS.len())]),
+                _ => {
+                    let len = rng.gen_range(1..=3).min(room);
+                    let n = rng.gen_range(10u32.pow(len as u32 - 1)..10u32.pow(len as u32));
+                    row.extend(n.to_string().bytes());
+                    if row.len() < width {
+                        row.push(b'.');
+                    }
+                }
+            }
+        }
+        grid.push(row);
+    }
+
+    let (part1, part2) = solve(&grid);
+    let rows = grid.into_iter()
+        .map(|r| String::from_utf8(r).unwrap())
+        .collect();
+
+    Puzzle::solved(rows, part1, part2)
+}
+
+// Straightforward reference solution: find every number, then look at
+// its neighbours for symbols and gears.
+fn solve(grid: &[Vec<u8>]) -> (u64, u64) {
+    let is_symbol = |x: i64, y: i64| -> Option<u8> {
+        let c = *grid.get(usize::try_from(y).ok()?)?.get(usize::try_from(x).ok()?)?;
+        SYMBOLS.contains(&c).then_some(c)
+    };
+
+    let mut part1 = 0u64;
+    let mut gears: HashMap<(i64, i64), Vec<u64>> = HashMap::new();
+
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            let n = std::str::from_utf8(&row[start..x]).unwrap().parse::<u64>().unwrap();
+
+            let mut is_part = false;
+            for ny in (y as i64 - 1)..=(y as i64 + 1) {
+                for nx in (start as i64 - 1)..=(x as i64) {
+                    match is_symbol(nx, ny) {
+                        Some(b'*') => {
+                            is_part = true;
+                            gears.entry((nx, ny)).or_default().push(n);
+                        }
+                        Some(_) => is_part = true,
+                        None => {}
+                    }
+                }
+            }
+            if is_part {
+                part1 += n;
+            }
+        }
+    }
+
+    let part2 = gears.values()
+        .filter(|v| v.len() == 2)
+        .map(|v| v[0] * v[1])
+        .sum();
+
+    (part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 40, 40), generate(7, 40, 40));
+        assert_ne!(generate(7, 40, 40), generate(8, 40, 40));
+    }
+
+    #[test]
+    fn grid_has_requested_size() {
+        let p = generate(5, 33, 21);
+        let lines = p.input.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 21);
+        assert!(lines.iter().all(|l| l.len() == 33));
+    }
+
+    #[test]
+    fn solve_sample() {
+        let input = [
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ];
+        let grid = input.iter().map(|r| r.bytes().collect()).collect::<Vec<_>>();
+
+        assert_eq!(solve(&grid), (4361, 467835));
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 20, 10);
+            assert_eq!(p.part1, Some(day_03::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_03::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
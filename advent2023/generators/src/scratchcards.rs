@@ -0,0 +1,79 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::Puzzle;
+
+/// Day 4 scratchcards, each with `winners` winning numbers and `numbers`
+/// numbers drawn from 1 to 99. No card wins copies past the end of the
+/// table, as the puzzle promises.
+pub fn generate(seed: u64, cards: usize, winners: usize, numbers: usize) -> Puzzle {
+    assert!(winners + numbers <= 99, "not enough distinct numbers for a card");
+
+    let mut rng = crate::rng(seed);
+    let pool = (1..100u32).collect::<Vec<_>>();
+
+    let mut rows = vec![];
+    let mut matches = vec![];
+
+    for id in 1..=cards {
+        let limit = winners.min(numbers).min(cards - id);
+        let m = match rng.gen_bool(0.5) {
+            true => rng.gen_range(0..=limit),
+            false => 0,
+        };
+
+        let drawn = pool.choose_multiple(&mut rng, winners + numbers - m).cloned().collect::<Vec<_>>();
+        let (wins, rest) = drawn.split_at(winners);
+
+        let mut have = [&wins[..m], rest].concat();
+        have.shuffle(&mut rng);
+
+        let join = |v: &[u32]| v.iter().map(|x| format!("{x:>2}")).collect::<Vec<_>>().join(" ");
+        rows.push(format!("Card {id:>3}: {} | {}", join(wins), join(&have)));
+        matches.push(m);
+    }
+
+    let part1 = matches.iter()
+        .filter(|&&m| m > 0)
+        .map(|&m| 1u64 << (m - 1))
+        .sum::<u64>();
+
+    let mut copies = vec![1u64; cards];
+    for i in 0..cards {
+        for j in (i + 1)..=(i + matches[i]) {
+            copies[j] += copies[i];
+        }
+    }
+    let part2 = copies.iter().sum::<u64>();
+
+    Puzzle::solved(rows, part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        assert_eq!(generate(7, 100, 10, 25), generate(7, 100, 10, 25));
+        assert_ne!(generate(7, 100, 10, 25), generate(8, 100, 10, 25));
+    }
+
+    #[test]
+    fn last_card_never_wins() {
+        let p = generate(2, 30, 5, 8);
+        let last = p.input.lines().last().unwrap();
+        let (wins, have) = last.split_once(':').unwrap().1.split_once('|').unwrap();
+
+        let wins = wins.split_whitespace().collect::<Vec<_>>();
+        assert!(have.split_whitespace().all(|n| !wins.contains(&n)));
+    }
+
+    #[test]
+    fn answers_match_solver() {
+        for seed in 0..20 {
+            let p = generate(seed, 6, 5, 8);
+            assert_eq!(p.part1, Some(day_04::solve_part1(&p.input)), "seed {seed}");
+            assert_eq!(p.part2, Some(day_04::solve_part2(&p.input)), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! `#[derive(AocParse)]`, used through `shared::parse`, which describes
+//! the parsers it generates.
+
+use std::collections::HashMap;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, LitStr};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Implements `FromStr` and `shared::parse::Field` for a struct, from the
+/// pattern in its `#[aoc("...")]` attribute. A struct with a lifetime gets
+/// `TryFrom<&str>` for that lifetime instead of `FromStr`.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(ident, "AocParse can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(ident, "AocParse needs a struct with named fields"));
+    };
+
+    let attr = input.attrs.iter()
+        .find(|a| a.path().is_ident("aoc"))
+        .ok_or_else(|| Error::new_spanned(ident, "missing #[aoc(\"...\")] with the pattern to parse"))?;
+    let pattern: LitStr = attr.parse_args()?;
+    let segments = segments(&pattern.value()).map_err(|e| Error::new(pattern.span(), e))?;
+
+    // Every field of the struct, once each
+    let mut idents = fields.named.iter()
+        .filter_map(|f| f.ident.as_ref())
+        .map(|i| (i.to_string(), i))
+        .collect::<HashMap<_, _>>();
+    let mut steps = vec![];
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(lit) => steps.push(quote! { __cursor.literal(#lit)?; }),
+            Segment::Field(name) => {
+                let field = idents.remove(name)
+                    .ok_or_else(|| Error::new(pattern.span(), format!("`{name}` is not a field of {ident}, or is in the pattern twice")))?;
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(lit)) => quote! { ::std::option::Option::Some(#lit) },
+                    _ => quote! { ::std::option::Option::None },
+                };
+                steps.push(quote! { let #field = __cursor.field(#name, #until)?; });
+            }
+        }
+    }
+    if let Some(missing) = fields.named.iter().filter_map(|f| f.ident.as_ref()).find(|i| idents.contains_key(&i.to_string())) {
+        return Err(Error::new_spanned(missing, format!("`{missing}` is not in the pattern")));
+    }
+
+    let names = fields.named.iter().map(|f| &f.ident);
+    let body = quote! {
+        let mut __cursor = ::shared::parse::Cursor::new(s);
+        #(#steps)*
+        __cursor.end()?;
+        ::std::result::Result::Ok(#ident { #(#names),* })
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // A struct that borrows from the line is parsed for as long as its
+    // lifetime; one that does not can be parsed from any line
+    if let Some(lifetime) = input.generics.lifetimes().next().map(|l| &l.lifetime) {
+        return Ok(quote! {
+            impl #impl_generics ::std::convert::TryFrom<&#lifetime str> for #ident #ty_generics #where_clause {
+                type Error = ::shared::parse::ParseError;
+
+                fn try_from(s: &#lifetime str) -> ::std::result::Result<Self, Self::Error> {
+                    #body
+                }
+            }
+
+            impl #impl_generics ::shared::parse::Field<#lifetime> for #ident #ty_generics #where_clause {
+                fn parse_field(text: &#lifetime str) -> ::std::result::Result<Self, ::shared::parse::FieldError> {
+                    Self::try_from(text).map_err(::shared::parse::FieldError::from)
+                }
+            }
+        });
+    }
+
+    let mut field_generics = input.generics.clone();
+    field_generics.params.insert(0, parse_quote!('__aoc));
+    let (field_impl_generics, _, _) = field_generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::shared::parse::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+
+        impl #field_impl_generics ::shared::parse::Field<'__aoc> for #ident #ty_generics #where_clause {
+            fn parse_field(text: &'__aoc str) -> ::std::result::Result<Self, ::shared::parse::FieldError> {
+                text.parse::<Self>().map_err(::shared::parse::FieldError::from)
+            }
+        }
+    })
+}
+
+// Splits a pattern like `Card {id}: {winners}` into its literals and
+// fields. `{{` and `}}` stand for literal braces.
+fn segments(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                literal.push(c);
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_alphanumeric() || c == '_' => name.push(c),
+                        Some(c) => return Err(format!("`{c}` can not be part of a field name")),
+                        None => return Err("`{` is not closed, write `{{` for a literal one".to_string()),
+                    }
+                }
+                if name.is_empty() {
+                    return Err("`{}` needs a field name".to_string());
+                }
+                match (segments.last(), literal.is_empty()) {
+                    (Some(Segment::Field(prev)), true) => {
+                        return Err(format!("`{{{prev}}}` and `{{{name}}}` need something between them"));
+                    }
+                    (_, false) => segments.push(Segment::Literal(std::mem::take(&mut literal))),
+                    _ => {}
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("unmatched `}`, write `}}` for a literal one".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Segment::*;
+
+    #[test]
+    fn split_pattern() {
+        assert_eq!(segments("Card {id}: {winners} | {numbers}").unwrap(), vec![
+            Literal("Card ".to_string()),
+            Field("id".to_string()),
+            Literal(": ".to_string()),
+            Field("winners".to_string()),
+            Literal(" | ".to_string()),
+            Field("numbers".to_string()),
+        ]);
+        assert_eq!(segments("{{{a}}}").unwrap(), vec![Literal("{".to_string()), Field("a".to_string()), Literal("}".to_string())]);
+
+        assert!(segments("{a}{b}").unwrap_err().contains("need something between"));
+        assert!(segments("{a").is_err());
+        assert!(segments("a}").is_err());
+        assert!(segments("{a b}").is_err());
+        assert!(segments("{}").is_err());
+    }
+}
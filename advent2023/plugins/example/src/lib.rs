@@ -0,0 +1,44 @@
+//! An example solver plugin: day 9 by binomial coefficients instead of
+//! rows of differences. Build it, then copy the library into the runner's
+//! `plugin_dir` and pick it with `--variant binomial`.
+
+// The value after `values`, which a polynomial of lower degree than their
+// count fits: the sum of each value times a signed binomial coefficient
+fn extrapolate(values: &[i128]) -> i128 {
+    let n = values.len() as i128;
+    let mut coeff = n;
+    let mut next = 0;
+    for (i, v) in values.iter().enumerate().rev() {
+        next += coeff * v;
+        let i = i as i128;
+        coeff = -coeff * i / (n - i + 1);
+    }
+    next
+}
+
+fn solve(input: &str, backwards: bool) -> String {
+    input.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let mut values = l.split_whitespace().map(|x| x.parse::<i128>().expect("not a number")).collect::<Vec<_>>();
+            if backwards {
+                values.reverse();
+            }
+            extrapolate(&values)
+        })
+        .sum::<i128>()
+        .to_string()
+}
+
+fn solve_part1(input: &str) -> String {
+    solve(input, false)
+}
+
+fn solve_part2(input: &str) -> String {
+    solve(input, true)
+}
+
+shared::aoc_plugin! {
+    (2023, 9, 1, "binomial") => solve_part1,
+    (2023, 9, 2, "binomial") => solve_part2,
+}
@@ -0,0 +1,545 @@
+//! Python bindings for the solvers and their models, built with
+//! `maturin develop` in `python/`:
+//!
+//! ```python
+//! import advent2023 as aoc
+//!
+//! aoc.solve(7, 1, open("day-07/input1.txt").read())
+//! hands = [aoc.Hand(line) for line in ["32T3K 765", "KK677 28"]]
+//! aoc.rank_winnings(hands), max(hands).hand_type
+//! aoc.PipeMaze(text).main_loop()
+//! ```
+//!
+//! Parsers raise `ValueError` on input they can not read. Solvers release
+//! the GIL while they run.
+
+#![cfg(feature = "python")]
+
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::panic::{self, UnwindSafe};
+use std::sync::Once;
+use pyo3::exceptions::{PyIndexError, PyOverflowError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use runner::registry;
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "non-string panic".to_string(),
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs `f`, giving back the message of any panic. The panic is raised in
+// Python instead, so it is kept off stderr.
+fn catch<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, String> {
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                default(info);
+            }
+        }));
+    });
+
+    CATCHING.set(true);
+    let result = panic::catch_unwind(f);
+    CATCHING.set(false);
+    result.map_err(|e| message(&*e))
+}
+
+// Runs one of the days' parsers, which panic on bad input, raising a
+// ValueError instead
+fn parse<T>(what: &str, f: impl FnOnce() -> T + UnwindSafe) -> PyResult<T> {
+    catch(f).map_err(|e| PyValueError::new_err(format!("unable to parse {what}: {e}")))
+}
+
+/// Solves `part` of `day` for the given puzzle input, with the runner's
+/// default implementation. `year` defaults to the latest one, as in the
+/// runner.
+#[pyfunction]
+#[pyo3(signature = (day, part, input, year = None))]
+fn solve(py: Python, day: u32, part: u32, input: &str, year: Option<u32>) -> PyResult<String> {
+    let year = year.unwrap_or_else(registry::latest_year);
+    let solver = registry::find(year, day)
+        .and_then(|d| d.variant(part, registry::DEFAULT_VARIANT))
+        .ok_or_else(|| PyValueError::new_err(format!("no solver for {year} day {day} part {part}")))?;
+    let input = input.to_string();
+    py.allow_threads(move || catch(move || solver.solve(&input)))
+        .map_err(|e| PyRuntimeError::new_err(format!("day {day} part {part} failed: {e}")))
+}
+
+/// Day 1: the first and last digit in a line. With `use_words`, spelled
+/// out digits count too.
+#[pyfunction]
+#[pyo3(signature = (line, use_words = false))]
+fn find_numbers(line: &str, use_words: bool) -> (u32, u32) {
+    day_01::find_numbers(line, use_words)
+}
+
+/// Day 2: one game record.
+#[pyclass(name = "CubeGame", module = "advent2023", frozen)]
+struct CubeGame(day_02::CubeGame);
+
+#[pymethods]
+impl CubeGame {
+    #[new]
+    fn new(line: &str) -> PyResult<Self> {
+        let line = line.to_string();
+        parse("the game", move || CubeGame(day_02::parse_game(&line)))
+    }
+
+    #[getter]
+    fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    /// Each handful as `(red, green, blue)`.
+    #[getter]
+    fn reveals(&self) -> Vec<(i32, i32, i32)> {
+        self.0.rev.iter().map(|r| (r.red, r.green, r.blue)).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CubeGame({:?})", self.0.to_string())
+    }
+}
+
+/// Day 3: the engine schematic, with its part numbers and gear ratios
+/// worked out.
+#[pyclass(name = "Engine", module = "advent2023", frozen)]
+struct Engine(day_03::Engine);
+
+#[pymethods]
+impl Engine {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let lines = shared::input_as_lines(input);
+        parse("the schematic", move || Engine(day_03::Engine::from(lines.as_slice())))
+    }
+
+    /// `(width, height)`
+    #[getter]
+    fn size(&self) -> (usize, usize) {
+        (self.0.len_x(), self.0.len_y())
+    }
+
+    /// The character at `(x, y)`.
+    fn code(&self, x: i32, y: i32) -> PyResult<char> {
+        self.0.get(x, y).map(|c| c.code).ok_or_else(|| PyIndexError::new_err(format!("({x}, {y}) is off the schematic")))
+    }
+
+    /// Part numbers, keyed by where each starts.
+    fn parts(&self) -> HashMap<(i32, i32), u32> {
+        self.components(|c| c.part)
+    }
+
+    /// Gear ratios, keyed by where each gear is.
+    fn gear_ratios(&self) -> HashMap<(i32, i32), u32> {
+        self.components(|c| c.ratio)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Engine {
+    fn components(&self, value: fn(&day_03::EngineComponent) -> u32) -> HashMap<(i32, i32), u32> {
+        let rows = self.0.schematic.iter().enumerate();
+        rows.flat_map(|(y, row)| row.iter().enumerate().map(move |(x, c)| ((x as i32, y as i32), value(c))))
+            .filter(|(_, v)| *v != 0)
+            .collect()
+    }
+}
+
+/// Day 4: a scratchcard.
+#[pyclass(name = "Card", module = "advent2023", frozen)]
+struct Card(day_04::Card);
+
+#[pymethods]
+impl Card {
+    #[new]
+    fn new(line: &str) -> PyResult<Self> {
+        line.parse().map(Card).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[getter]
+    fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    #[getter]
+    fn winners(&self) -> Vec<u32> {
+        self.0.winners.clone()
+    }
+
+    #[getter]
+    fn numbers(&self) -> Vec<u32> {
+        self.0.numbers.clone()
+    }
+
+    /// Numbers you have that are also winning numbers.
+    fn matches(&self) -> Vec<u32> {
+        self.0.matches()
+    }
+
+    /// One point for the first match, doubled for each match after.
+    fn points(&self) -> PyResult<u64> {
+        self.0.points().ok_or_else(|| PyOverflowError::new_err("too many matches"))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Card({:?})", self.0.to_string())
+    }
+}
+
+/// Day 5: one line of an almanac map.
+#[pyclass(name = "GardenMap", module = "advent2023", frozen, get_all)]
+struct GardenMap {
+    dest: i64,
+    src: i64,
+    len: i64,
+}
+
+#[pymethods]
+impl GardenMap {
+    fn __repr__(&self) -> String {
+        format!("GardenMap(dest={}, src={}, len={})", self.dest, self.src, self.len)
+    }
+}
+
+/// Day 5: the almanac, its seeds and the maps from seed to location.
+#[pyclass(name = "Garden", module = "advent2023", frozen)]
+struct Garden(day_05::Garden);
+
+#[pymethods]
+impl Garden {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let lines = shared::input_as_lines(input);
+        parse("the almanac", move || Garden(day_05::Garden::from(lines.as_slice())))
+    }
+
+    #[getter]
+    fn seeds(&self) -> Vec<i64> {
+        self.0.seeds.clone()
+    }
+
+    /// Each map, in order from seed-to-soil to humidity-to-location.
+    #[getter]
+    fn maps(&self) -> Vec<Vec<GardenMap>> {
+        let g = &self.0;
+        [&g.seed_to_soil, &g.soil_to_fert, &g.fert_to_water, &g.water_to_light, &g.light_to_temp, &g.temp_to_humid, &g.humid_to_loc]
+            .into_iter()
+            .map(|m| m.iter().map(|x| GardenMap { dest: x.dest, src: x.src, len: x.len }).collect())
+            .collect()
+    }
+
+    /// Location for a single seed.
+    fn location(&self, seed: i64) -> i64 {
+        self.0.find_loc_part1(seed)
+    }
+
+    /// Lowest location for any seed in `start..start + len`, or None if
+    /// the range is empty.
+    fn lowest_location(&self, py: Python, start: i64, len: i64) -> Option<i64> {
+        py.allow_threads(|| self.0.find_loc_part2_intervals(start, len))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Day 6: a race and its record distance.
+#[pyclass(name = "BoatRace", module = "advent2023", frozen, get_all)]
+struct BoatRace {
+    time: i64,
+    dist: i64,
+}
+
+impl From<day_06::BoatRace> for BoatRace {
+    fn from(r: day_06::BoatRace) -> Self {
+        BoatRace { time: r.time, dist: r.dist }
+    }
+}
+
+#[pymethods]
+impl BoatRace {
+    #[new]
+    fn new(time: i64, dist: i64) -> Self {
+        BoatRace { time, dist }
+    }
+
+    /// One race per column of the `Time:` and `Distance:` lines.
+    #[staticmethod]
+    fn parse_races(input: &str) -> PyResult<Vec<BoatRace>> {
+        let input = input.to_string();
+        parse("the races", move || day_06::BoatRace::parse_races(&input).into_iter().map(BoatRace::from).collect())
+    }
+
+    /// The same lines read as one race, ignoring the spaces.
+    #[staticmethod]
+    fn parse_kerned(input: &str) -> PyResult<BoatRace> {
+        let input = input.to_string();
+        parse("the race", move || day_06::BoatRace::parse_kerned(&input).into())
+    }
+
+    /// Number of hold times that beat the record.
+    fn wins(&self, py: Python) -> i64 {
+        let race = day_06::BoatRace { time: self.time, dist: self.dist };
+        py.allow_threads(|| race.find_wins())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BoatRace(time={}, dist={})", self.time, self.dist)
+    }
+}
+
+/// Day 7: a hand of camel cards and its bid. Hands compare by strength,
+/// so `sorted(hands)` ranks them weakest first.
+#[pyclass(name = "Hand", module = "advent2023", frozen)]
+struct Hand(day_07::Hand);
+
+impl Hand {
+    fn model(&self) -> day_07::Hand {
+        day_07::Hand { bid: self.0.bid, htype: self.0.htype, cards: self.0.cards }
+    }
+}
+
+#[pymethods]
+impl Hand {
+    #[new]
+    fn new(line: &str) -> PyResult<Self> {
+        let line = line.to_string();
+        parse("the hand", move || Hand(day_07::Hand::from(line.as_str())))
+    }
+
+    #[getter]
+    fn bid(&self) -> u64 {
+        self.0.bid
+    }
+
+    /// The card labels, e.g. `"32T3K"`.
+    #[getter]
+    fn cards(&self) -> String {
+        self.0.cards.iter().map(|c| day_07::Hand::card_char(*c)).collect()
+    }
+
+    /// The kind of hand, e.g. `"FullHouse"`.
+    #[getter]
+    fn hand_type(&self) -> String {
+        format!("{:?}", self.0.htype)
+    }
+
+    fn strength(&self) -> u64 {
+        self.0.strength()
+    }
+
+    fn __lt__(&self, other: PyRef<Hand>) -> bool {
+        self.0.strength() < other.0.strength()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Hand({:?})", self.0.to_string())
+    }
+}
+
+/// Day 7: the sum of each hand's bid times its rank among `hands`.
+#[pyfunction]
+fn rank_winnings(hands: Vec<PyRef<Hand>>) -> u64 {
+    day_07::rank_winnings(hands.iter().map(|h| h.model()).collect())
+}
+
+/// Day 8: the instructions and the map of nodes.
+#[pyclass(name = "CamelNetwork", module = "advent2023", frozen)]
+struct CamelNetwork(day_08::CamelNetwork);
+
+#[pymethods]
+impl CamelNetwork {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let input = input.to_string();
+        parse("the network", move || CamelNetwork(day_08::CamelNetwork::from(input.as_str())))
+    }
+
+    #[getter]
+    fn instructions(&self) -> String {
+        self.0.inst.clone()
+    }
+
+    /// Each node's `(left, right)` neighbours.
+    #[getter]
+    fn nodes(&self) -> HashMap<String, (String, String)> {
+        self.0.nodes().map(|(n, (l, r))| (n.to_string(), (l.to_string(), r.to_string()))).collect()
+    }
+
+    /// Steps from `AAA` to `ZZZ`, or None if it can not be reached.
+    fn steps(&self, py: Python) -> Option<u32> {
+        py.allow_threads(|| self.0.find_steps_pt1())
+    }
+
+    /// Steps until every ghost is on a node ending in `Z`.
+    fn ghost_steps(&self, py: Python) -> u64 {
+        py.allow_threads(|| self.0.find_steps_pt2())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Day 9: the values of one reading.
+#[pyclass(name = "History", module = "advent2023", frozen)]
+struct History(day_09::History);
+
+#[pymethods]
+impl History {
+    #[new]
+    fn new(line: &str) -> PyResult<Self> {
+        line.parse().map(History).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[getter]
+    fn values(&self) -> Vec<i64> {
+        self.0.input.clone()
+    }
+
+    /// The next value in the sequence.
+    fn next(&self) -> i64 {
+        self.0.next_seq()
+    }
+
+    /// The history backwards, whose next value comes before this one.
+    fn reverse(&self) -> History {
+        History(self.0.reverse())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("History({:?})", self.0.to_string())
+    }
+}
+
+/// Day 10: the pipe maze, keyed by `(x, y)`.
+#[pyclass(name = "PipeMaze", module = "advent2023", frozen)]
+struct PipeMaze(day_10::PipeMaze);
+
+#[pymethods]
+impl PipeMaze {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let input = input.to_string();
+        parse("the maze", move || PipeMaze(day_10::PipeMaze::from(input.as_str())))
+    }
+
+    #[getter]
+    fn start(&self) -> (i32, i32) {
+        self.0.start
+    }
+
+    /// `(width, height)`
+    #[getter]
+    fn size(&self) -> (i32, i32) {
+        self.0.size
+    }
+
+    /// The pipe at `(x, y)` as its input character, with `.` for ground.
+    fn tile(&self, x: i32, y: i32) -> char {
+        self.0.get_segment_type((x, y)).symbol()
+    }
+
+    /// Every tile on the loop through the start.
+    fn main_loop(&self) -> HashSet<(i32, i32)> {
+        self.0.find_main_loop().into_iter().collect()
+    }
+
+    /// Tiles enclosed by the main loop.
+    fn nest(&self) -> HashSet<(i32, i32)> {
+        self.0.nest_tiles().into_iter().collect()
+    }
+
+    fn farthest(&self) -> i32 {
+        self.0.find_farthest()
+    }
+
+    /// The loop drawn with box characters and the nest filled with `I`.
+    fn draw(&self) -> String {
+        self.0.draw_loop()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[pymodule]
+fn advent2023(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(find_numbers, m)?)?;
+    m.add_function(wrap_pyfunction!(rank_winnings, m)?)?;
+    m.add_class::<CubeGame>()?;
+    m.add_class::<Engine>()?;
+    m.add_class::<Card>()?;
+    m.add_class::<GardenMap>()?;
+    m.add_class::<Garden>()?;
+    m.add_class::<BoatRace>()?;
+    m.add_class::<Hand>()?;
+    m.add_class::<CamelNetwork>()?;
+    m.add_class::<History>()?;
+    m.add_class::<PipeMaze>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::ffi::c_str;
+
+    #[test]
+    fn call_from_python() {
+        pyo3::append_to_inittab!(advent2023);
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            py.run(c_str!(r#"
+import advent2023 as aoc
+
+assert aoc.solve(9, 1, "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45") == "114"
+assert aoc.solve(5, 2, "seeds: 0 100000000000\n\nseed-to-soil map:\n1 5 1") == "0"
+assert aoc.solve(9, 1, "1 2 3", year=2023) == "4"
+
+hands = [aoc.Hand(l) for l in ["32T3K 765", "T55J5 684", "KK677 28", "KTJJT 220", "QQQJA 483"]]
+assert aoc.rank_winnings(hands) == 6440
+assert (max(hands).cards, max(hands).hand_type) == ("QQQJA", "ThreeOfAKind")
+
+maze = aoc.PipeMaze(".....\n.S-7.\n.|.|.\n.L-J.\n.....")
+assert (maze.farthest(), maze.nest(), maze.tile(3, 1)) == (4, {(2, 2)}, "7")
+
+card = aoc.Card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")
+assert (card.matches(), card.points()) == ([83, 86, 17, 48], 8)
+
+for bad in [lambda: aoc.Card("Card x: 1 | 2"), lambda: aoc.Hand("32T3K"), lambda: aoc.solve(11, 1, ""),
+            lambda: aoc.solve(9, 1, "1 2 3", 2015)]:
+    try:
+        bad()
+        raise AssertionError("no error")
+    except ValueError:
+        pass
+
+try:
+    aoc.solve(2, 1, "nonsense")
+except RuntimeError as e:
+    assert str(e).startswith("day 2 part 1 failed: ")
+    assert "line 1: Game could not be parsed" in str(e)
+"#), None, None).unwrap();
+        });
+    }
+}
@@ -0,0 +1,352 @@
+//! The solvers over HTTP, for tools that would rather not shell out to
+//! `aoc`.
+//!
+//! - `POST /solve/{day}/{part}` solves the puzzle input in the body, of up
+//!   to [`MAX_BODY`] bytes. The query can pick the `year`, a `variant`,
+//!   and a `timeout` in seconds, no longer than the server's own.
+//! - `GET /days` lists the registered days, with their parts and variants.
+//!
+//! Replies are JSON; a failed request gets an `error` message. Slow parts
+//! that can not be cancelled are refused, since one that timed out would
+//! keep its thread busy for minutes. Other parts that can not be
+//! cancelled and run on after timing out are counted, and new solves are
+//! refused while too many are.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde_json::{json, Value};
+use shared::cancel::Cancel;
+use crate::registry::{self, Day, DAYS};
+use crate::run::{self, Status};
+
+/// Largest puzzle input accepted, in bytes.
+pub const MAX_BODY: usize = 1 << 20;
+
+/// What a request gets when it does not say, and what it may not ask
+/// past.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub year: u32,
+    /// Longest a part may run; a request can only ask for less.
+    pub timeout: Duration,
+    /// Solvers still running after timing out that new solves wait for.
+    pub max_overrunning: usize,
+    overrunning: Overrunning,
+}
+
+impl Options {
+    pub fn new(year: u32, timeout: Duration) -> Self {
+        Options { year, timeout, max_overrunning: 4, overrunning: Overrunning::default() }
+    }
+}
+
+// Tokens of the solvers that timed out, shared by every worker
+#[derive(Clone, Default)]
+struct Overrunning(Arc<Mutex<Vec<Cancel>>>);
+
+impl Overrunning {
+    // How many are still running, forgetting those that have finished
+    fn count(&self) -> usize {
+        let mut tokens = self.0.lock().unwrap();
+        tokens.retain(|c| c.in_use());
+        tokens.len()
+    }
+
+    fn add(&self, cancel: Cancel) {
+        self.0.lock().unwrap().push(cancel);
+    }
+}
+
+impl fmt::Debug for Overrunning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Overrunning({})", self.count())
+    }
+}
+
+/// A status code and the JSON body to send with it.
+pub type Reply = (u16, Value);
+
+fn error(code: u16, message: impl Into<String>) -> Reply {
+    (code, json!({ "error": message.into() }))
+}
+
+/// Answers one request.
+pub fn handle(method: &str, url: &str, body: &[u8], options: &Options) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+
+    match (method, &segments[..]) {
+        ("GET", ["days"]) => (200, days()),
+        ("POST", ["solve", day, part]) => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part)) => solve(day, part, query, body, options).unwrap_or_else(|e| e),
+            _ => error(404, format!("no day {day} part {part}")),
+        },
+        (_, ["days"] | ["solve", _, _]) => error(405, format!("{method} is not allowed on {path}")),
+        _ => error(404, format!("nothing at {path}")),
+    }
+}
+
+// A query string's `%XX` escapes and `+` spaces decoded
+fn decode(text: &str) -> Result<String, Reply> {
+    let bad = || error(400, format!("bad escape in {text:?}"));
+    let mut bytes = vec![];
+    let mut rest = text.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest.get(..2).and_then(|h| std::str::from_utf8(h).ok()).ok_or_else(bad)?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| bad())?);
+                rest = &rest[2..];
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| bad())
+}
+
+fn solve(day: u32, part: u32, query: &str, body: &[u8], options: &Options) -> Result<Reply, Reply> {
+    let (mut year, mut timeout, mut variant) = (options.year, options.timeout, None);
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        match (decode(key)?.as_str(), decode(value)?) {
+            ("year", v) => year = v.parse().map_err(|_| error(400, format!("bad year {v:?}")))?,
+            ("timeout", v) => {
+                timeout = v.parse().ok()
+                    .and_then(|s| Duration::try_from_secs_f64(s).ok())
+                    .ok_or_else(|| error(400, format!("bad timeout {v:?}")))?
+                    .min(options.timeout);
+            }
+            ("variant", v) => variant = Some(v),
+            (key, _) => return Err(error(400, format!("unknown parameter {key}"))),
+        }
+    }
+
+    let d = registry::find(year, day).ok_or_else(|| error(404, format!("day {day} of {year} is not registered")))?;
+    let names = d.variants(part).into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+    if names.is_empty() {
+        return Err(error(404, format!("day {day} has no part {part}")));
+    }
+    let variant = match variant {
+        Some(name) => Some(*names.iter().find(|n| **n == name).ok_or_else(|| {
+            error(400, format!("day {day} part {part} has no variant {name}, only {}", names.join(", ")))
+        })?),
+        None => None,
+    };
+    cancellable(d, part, variant.unwrap_or(registry::DEFAULT_VARIANT))?;
+
+    let input = std::str::from_utf8(body).map_err(|_| error(400, "the input is not UTF-8"))?;
+    if input.trim().is_empty() {
+        return Err(error(400, "the body should hold the puzzle input"));
+    }
+
+    let overrunning = options.overrunning.count();
+    if overrunning >= options.max_overrunning {
+        return Err(error(503, format!("{overrunning} timed out solvers are still running, try again later")));
+    }
+
+    let cancel = Cancel::default();
+    let Some(r) = run::run_part_within(d, part, variant, input, Some(timeout), cancel.clone()) else {
+        return Err(error(404, format!("day {day} has no part {part}")));
+    };
+    // A part that can be cancelled stops soon after timing out; anything
+    // else runs on until it is done
+    if r.status == Status::TimedOut && d.long_solver(part, variant.unwrap_or(registry::DEFAULT_VARIANT)).is_none() {
+        options.overrunning.add(cancel);
+    }
+
+    let mut diagnostics = vec![];
+    match &r.status {
+        Status::Solved if r.answer.is_empty() => diagnostics.push("the solver gave no answer".to_string()),
+        Status::Solved => {}
+        Status::TimedOut => diagnostics.push(format!("gave up after {timeout:.2?}")),
+        Status::Panicked(msg) => diagnostics.push(format!("the solver panicked: {msg}")),
+    }
+    if d.is_slow(part, variant.unwrap_or(registry::DEFAULT_VARIANT)) {
+        diagnostics.push(format!("part {part} is slow, and can take minutes"));
+    }
+    if input.contains('\r') {
+        diagnostics.push("the input has \\r\\n line endings, which some solvers do not expect".to_string());
+    }
+
+    let mut reply = r.to_json();
+    reply["title"] = json!(d.title);
+    reply["diagnostics"] = json!(diagnostics);
+    Ok((200, reply))
+}
+
+// Refuses slow parts that would run on after their time is up
+fn cancellable(d: &Day, part: u32, name: &str) -> Result<(), Reply> {
+    match d.is_slow(part, name) && d.long_solver(part, name).is_none() {
+        true => Err(error(400, format!("day {} part {part} ({name}) is slow and can not be cancelled", d.day))),
+        false => Ok(()),
+    }
+}
+
+fn days() -> Value {
+    DAYS.iter()
+        .map(|d| json!({
+            "year": d.year,
+            "day": d.day,
+            "title": d.title,
+            "parts": (1..=2).map(|p| json!({
+                "part": p,
+                "variants": d.variants(p).into_iter().map(|(n, _)| n).collect::<Vec<_>>(),
+                "slow": d.is_slow(p, registry::DEFAULT_VARIANT),
+            })).collect::<Vec<_>>(),
+        }))
+        .collect()
+}
+
+/// Answers requests to `server` on `workers` threads, until it closes.
+/// Requests beyond that many wait their turn.
+#[cfg(any(feature = "server", test))]
+pub fn serve(server: tiny_http::Server, options: Options, workers: usize) {
+    use std::io::Read;
+
+    std::thread::scope(|s| {
+        for _ in 0..workers.max(1) {
+            s.spawn(|| {
+                while let Ok(mut request) = server.recv() {
+                    let mut body = vec![];
+                    let read = request.as_reader().take(MAX_BODY as u64 + 1).read_to_end(&mut body);
+                    let (code, reply) = match read {
+                        Ok(n) if n > MAX_BODY => error(413, format!("the body is over {MAX_BODY} bytes")),
+                        Ok(_) => handle(request.method().as_str(), request.url(), &body, &options),
+                        Err(e) => error(400, format!("unable to read the body: {e}")),
+                    };
+
+                    let json = tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap();
+                    let response = tiny_http::Response::from_string(reply.to_string())
+                        .with_status_code(code)
+                        .with_header(json);
+                    let _ = request.respond(response);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A server on a free port, and its address
+    fn start() -> String {
+        start_with(Options::new(2023, Duration::from_secs(10)))
+    }
+
+    fn start_with(options: Options) -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        std::thread::spawn(move || serve(server, options, 2));
+        url
+    }
+
+    fn reply(result: Result<ureq::Response, ureq::Error>) -> (u16, Value) {
+        let response = match result {
+            Ok(r) => r,
+            Err(ureq::Error::Status(_, r)) => r,
+            Err(e) => panic!("{e}"),
+        };
+        (response.status(), serde_json::from_str(&response.into_string().unwrap()).unwrap())
+    }
+
+    #[test]
+    fn solve_over_http() {
+        let url = start();
+        let post = |path: &str, body: &str| reply(ureq::post(&format!("{url}{path}")).send_string(body));
+
+        let (code, r) = post("/solve/9/1", "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45");
+        assert_eq!(code, 200);
+        assert_eq!((&r["answer"], &r["status"], &r["variant"]), (&json!("114"), &json!("solved"), &json!("default")));
+        assert_eq!((&r["title"], &r["diagnostics"]), (&json!("Mirage Maintenance"), &json!([])));
+        assert!(r["nanos"].is_u64());
+
+        let sample = std::fs::read_to_string(registry::find(2023, 5).unwrap().dir().join("input2.txt")).unwrap();
+        let (_, r) = post("/solve/5/2?year=2023", &sample);
+        assert_eq!((&r["answer"], &r["variant"], &r["diagnostics"]), (&json!("46"), &json!("default"), &json!([])));
+        let (_, r) = post("/solve/5/2?variant=brute", &sample);
+        assert_eq!((&r["answer"], &r["variant"]), (&json!("46"), &json!("brute")));
+        assert_eq!(r["diagnostics"], json!(["part 2 is slow, and can take minutes"]));
+
+        let (_, r) = post("/solve/5/2?variant=brute&timeout=0.05", "seeds: 0 100000000000\n\nseed-to-soil map:\n1 5 1");
+        assert_eq!((&r["status"], &r["diagnostics"]), (&json!("timed_out"), &json!(["gave up after 50.00ms", "part 2 is slow, and can take minutes"])));
+
+        let (code, r) = reply(ureq::get(&format!("{url}/days")).call());
+        assert_eq!(code, 200);
+        assert_eq!(r[4]["parts"][1]["variants"], json!(["default", "brute"]));
+    }
+
+    #[test]
+    fn hold_requests_to_limits() {
+        let mut options = Options::new(2023, Duration::from_millis(50));
+        options.max_overrunning = 1;
+        let url = start_with(options);
+        let post = |path: &str, body: &str| reply(ureq::post(&format!("{url}{path}")).send_string(body));
+
+        let (code, r) = post("/solve/9/1", &"0 ".repeat(MAX_BODY / 2 + 1));
+        assert_eq!((code, &r["error"]), (413, &json!(format!("the body is over {MAX_BODY} bytes"))));
+
+        // The server's timeout caps the one asked for
+        let brute = "seeds: 0 100000000000\n\nseed-to-soil map:\n1 5 1";
+        let (_, r) = post("/solve/5/2?variant=brute&timeout=1000", brute);
+        assert_eq!((&r["status"], &r["diagnostics"][0]), (&json!("timed_out"), &json!("gave up after 50.00ms")));
+
+        // A ghost going round a ring of nodes with no Z, which takes a long
+        // time to give up on and can not be stopped
+        let ring = (0..2000).map(|i| format!("N{i} = (N{0}, N{0})\n", (i + 1) % 2000)).collect::<String>();
+        let ghosts = format!("{}\n\nGGA = (N0, N0)\n{ring}", "L".repeat(100_000));
+        let (_, r) = post("/solve/8/2", &ghosts);
+        assert_eq!(r["status"], json!("timed_out"));
+
+        let (code, r) = post("/solve/9/1", "0 3 6");
+        assert_eq!((code, &r["error"]), (503, &json!("1 timed out solvers are still running, try again later")));
+    }
+
+    #[test]
+    fn reject_bad_requests() {
+        let options = Options::new(2023, Duration::from_secs(1));
+        let code = |method, url: &str, body: &str| handle(method, url, body.as_bytes(), &options).0;
+
+        assert_eq!(code("POST", "/solve/25/1", "x"), 404);
+        assert_eq!(code("POST", "/solve/9/3", "x"), 404);
+        assert_eq!(code("POST", "/solve/nine/1", "x"), 404);
+        assert_eq!(code("POST", "/solve/9/1?year=2015", "x"), 404);
+        assert_eq!(code("POST", "/solve/9/1?variant=nope", "x"), 400);
+        assert_eq!(code("POST", "/solve/9/1?timeout=-1", "x"), 400);
+        assert_eq!(code("POST", "/solve/9/1?colour=red", "x"), 400);
+        assert_eq!(code("POST", "/solve/9/1", " \n"), 400);
+        assert_eq!(code("GET", "/solve/9/1", ""), 405);
+        assert_eq!(code("GET", "/", ""), 404);
+
+        let (_, r) = handle("POST", "/solve/5/2?variant=fast", b"x", &options);
+        assert_eq!(r["error"], json!("day 5 part 2 has no variant fast, only default, brute"));
+        let (_, r) = handle("POST", "/solve/5/2?variant=br%75te+", b"x", &options);
+        assert_eq!(r["error"], json!("day 5 part 2 has no variant brute , only default, brute"));
+        assert_eq!(code("POST", "/solve/9/1?year=20%2", "x"), 400);
+        assert_eq!(code("POST", "/solve/9/1?%79ear=2023", "0 3 6"), 200);
+    }
+
+    #[test]
+    fn refuse_uncancellable_slow_parts() {
+        static SLOW: Day = Day {
+            year: 2023,
+            day: 9,
+            title: "Mirage Maintenance",
+            parts: [day_09::solve_part1, day_09::solve_part2],
+            slow: &[2],
+            viz: None,
+            long: &[],
+            variants: &[],
+            stream: &[],
+        };
+
+        assert!(cancellable(&SLOW, 1, registry::DEFAULT_VARIANT).is_ok());
+        let (code, r) = cancellable(&SLOW, 2, registry::DEFAULT_VARIANT).unwrap_err();
+        assert_eq!((code, &r["error"]), (400, &json!("day 9 part 2 (default) is slow and can not be cancelled")));
+        assert!(cancellable(registry::find(2023, 5).unwrap(), 2, "brute").is_ok());
+    }
+}
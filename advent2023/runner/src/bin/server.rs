@@ -0,0 +1,49 @@
+use std::time::Duration;
+use clap::Parser;
+use miette::{miette, Result};
+use runner::api::{self, Options};
+use runner::{plugin, registry};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: shared::alloc::CountingAlloc = shared::alloc::CountingAlloc;
+
+#[derive(Parser)]
+#[command(name = "server", about = "Advent of Code 2023 solvers over HTTP")]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8023")]
+    addr: String,
+    /// Year to solve when a request does not say; defaults to the latest
+    #[arg(long)]
+    year: Option<u32>,
+    /// Seconds a part may run before it is given up on
+    #[arg(long, default_value_t = 30.0)]
+    timeout: f64,
+    /// Requests to answer at once; the rest wait their turn
+    #[arg(long, default_value_t = 4)]
+    workers: usize,
+    /// Solvers that timed out but are still running, past which new
+    /// solves are refused
+    #[arg(long, default_value_t = 4)]
+    max_overrunning: usize,
+    /// Load solver plugins from DIR
+    #[arg(long, value_name = "DIR")]
+    plugin_dir: Option<std::path::PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let timeout = Duration::try_from_secs_f64(cli.timeout).map_err(|e| miette!("bad timeout: {e}"))?;
+    if let Some(dir) = &cli.plugin_dir {
+        plugin::load_dir(dir).map_err(|e| miette!("{e}"))?;
+    }
+    let server = tiny_http::Server::http(&cli.addr)
+        .map_err(|e| miette!("unable to listen on {}: {e}", cli.addr))?;
+
+    eprintln!("listening on http://{}", server.server_addr());
+    let mut options = Options::new(cli.year.unwrap_or_else(registry::latest_year), timeout);
+    options.max_overrunning = cli.max_overrunning;
+    api::serve(server, options, cli.workers);
+    Ok(())
+}
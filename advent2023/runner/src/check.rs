@@ -0,0 +1,155 @@
+use std::time::Duration;
+use generators::{almanac, calibration, camel_network, cube_games, oasis, pipe_maze, schematic, scratchcards};
+use shared::cancel::{self, Cancel, Outcome};
+use crate::registry::Day;
+
+/// An input to compare a day's variants on.
+pub struct Case {
+    pub name: String,
+    pub input: String,
+}
+
+/// A case the variants of a part gave different answers for.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub part: u32,
+    pub case: String,
+    /// Each variant's answer, or why it has none.
+    pub answers: Vec<(&'static str, String)>,
+}
+
+/// The day's sample inputs (`input2.txt` and on), its edge cases, then
+/// `generated` inputs from its generator, if it has one.
+pub fn cases(day: &Day, generated: usize) -> Vec<Case> {
+    let samples = (2..)
+        .map(|n| day.dir().join(format!("input{n}.txt")))
+        .take_while(|p| p.exists())
+        .filter_map(|p| {
+            let input = std::fs::read_to_string(&p).ok()?;
+            Some(Case { name: p.file_name()?.to_string_lossy().into_owned(), input })
+        });
+
+    let generated = (0..generated as u64)
+        .filter_map(|seed| Some(Case { name: format!("generated, seed {seed}"), input: generate(day, seed)? }));
+
+    let edges = edge_cases(day).iter()
+        .map(|&(name, input)| Case { name: name.to_string(), input: input.to_string() });
+
+    samples.chain(edges).chain(generated).collect()
+}
+
+// Inputs variants have disagreed on, which generated inputs are unlikely
+// to hit
+fn edge_cases(day: &Day) -> &'static [(&'static str, &'static str)] {
+    match (day.year, day.day) {
+        (2023, 5) => &[
+            ("lowest location 0", "seeds: 0 3\n\nseed-to-soil map:\n100 200 1\n"),
+            ("empty seed range", "seeds: 5 0\n\nseed-to-soil map:\n100 200 1\n"),
+            ("empty and non-empty seed ranges", "seeds: 5 0 10 3\n\nseed-to-soil map:\n100 200 1\n"),
+        ],
+        _ => &[],
+    }
+}
+
+// Small inputs, so that even brute force variants finish quickly
+fn generate(day: &Day, seed: u64) -> Option<String> {
+    let puzzle = match (day.year, day.day) {
+        (2023, 1) => calibration::generate(seed, 10, 6),
+        (2023, 2) => cube_games::generate(seed, 5, 4),
+        (2023, 3) => schematic::generate(seed, 20, 10),
+        (2023, 4) => scratchcards::generate(seed, 6, 5, 8),
+        (2023, 5) => almanac::generate_with(seed, 3, 4, 1000),
+        (2023, 8) => camel_network::generate(seed, 4, 2, 5),
+        (2023, 9) => oasis::generate(seed, 4, 8, 3),
+        (2023, 10) => pipe_maze::generate(seed, 10, 8, 1),
+        _ => return None,
+    };
+    Some(puzzle.input)
+}
+
+/// Runs every variant of each part on each case, giving each run up to
+/// `budget`. Cases the default implementation can not solve, like a
+/// sample meant for the other part, are not compared.
+pub fn check(day: &Day, cases: &[Case], budget: Duration) -> Vec<Disagreement> {
+    let mut found = vec![];
+
+    for part in 1..=2 {
+        let variants = day.variants(part);
+        if variants.len() < 2 {
+            continue;
+        }
+
+        for case in cases {
+            let answers = variants.iter()
+                .map(|&(name, solve)| {
+                    let input = case.input.clone();
+                    let answer = match cancel::within(budget, &Cancel::default(), move |_| solve.solve(&input)) {
+                        Outcome::Done(a) => Ok(a),
+                        Outcome::TimedOut => Err("timed out".to_string()),
+                        Outcome::Panicked(msg) => Err(format!("panicked: {msg}")),
+                    };
+                    (name, answer)
+                })
+                .collect::<Vec<_>>();
+
+            let Ok(expected) = &answers[0].1 else { continue };
+            if answers.iter().any(|(_, a)| a.as_ref() != Ok(expected)) {
+                found.push(Disagreement {
+                    part,
+                    case: case.name.clone(),
+                    answers: answers.into_iter().map(|(n, a)| (n, a.unwrap_or_else(|e| e))).collect(),
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{Variant, DAYS};
+
+    const BUDGET: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn variants_agree() {
+        for day in DAYS {
+            let found = check(day, &cases(day, 20), BUDGET);
+            assert!(found.is_empty(), "day {}: {found:?}", day.day);
+        }
+    }
+
+    #[test]
+    fn edge_cases_checked() {
+        let day = crate::registry::find(2023, 5).unwrap();
+        let cases = cases(day, 0);
+
+        assert!(cases.iter().any(|c| c.name == "empty seed range"));
+        assert!(check(day, &cases, BUDGET).is_empty());
+    }
+
+    #[test]
+    fn catch_disagreement() {
+        static BROKEN: Day = Day {
+            year: 2023,
+            day: 9,
+            title: "Mirage Maintenance",
+            parts: [day_09::solve_part1, day_09::solve_part2],
+            slow: &[],
+            viz: None,
+            long: &[],
+            variants: &[Variant { part: 2, name: "off-by-one", solve: |s| format!("{}1", day_09::solve_part2(s)), long: None, slow: false }],
+            stream: &[],
+        };
+
+        let cases = cases(&BROKEN, 2);
+        assert_eq!(cases.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            vec!["input2.txt", "generated, seed 0", "generated, seed 1"]);
+
+        let found = check(&BROKEN, &cases, BUDGET);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].part, 2);
+        assert_eq!(found[0].answers, vec![("default", "2".to_string()), ("off-by-one", "21".to_string())]);
+    }
+}
@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+use crate::registry::{self, Day};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Settings from `aoc.toml`. Anything missing keeps its default.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The event to work on; the latest registered one when unset.
+    pub year: Option<u32>,
+    /// Directory of `<year>/day-XX.txt` inputs, in place of each day's input1.txt.
+    pub input_dir: Option<PathBuf>,
+    /// Named account, whose session token is in `AOC_SESSION_<NAME>`.
+    pub account: Option<String>,
+    pub format: Format,
+    /// Seconds to wait for a part before giving up on it.
+    pub timeout: Option<u64>,
+    /// Where to write visualizations; none are drawn when unset.
+    pub viz_dir: Option<PathBuf>,
+    /// Days to solve, bench or list; all of them when unset.
+    pub days: Option<Vec<u32>>,
+    /// Also run parts that take minutes.
+    pub slow: bool,
+    /// Directory of solver plugins to load; none are loaded when unset.
+    pub plugin_dir: Option<PathBuf>,
+}
+
+/// Command line settings, each taking over from the one in `aoc.toml`.
+#[derive(Debug, Default, Args)]
+pub struct Overrides {
+    /// Config file to read instead of aoc.toml in the workspace
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Work on this year's puzzles
+    #[arg(long, global = true)]
+    pub year: Option<u32>,
+    /// Read each day's input from DIR/<year>/day-XX.txt
+    #[arg(long, global = true, value_name = "DIR")]
+    pub input_dir: Option<PathBuf>,
+    /// Use the session token in AOC_SESSION_<NAME>
+    #[arg(long, global = true, value_name = "NAME")]
+    pub account: Option<String>,
+    #[arg(long, global = true, value_enum)]
+    pub format: Option<Format>,
+    /// Seconds to wait for a part before giving up on it
+    #[arg(long, global = true, value_name = "SECS")]
+    pub timeout: Option<u64>,
+    /// Write visualizations into DIR
+    #[arg(long, global = true, value_name = "DIR")]
+    pub viz_dir: Option<PathBuf>,
+    /// Only these days, comma separated
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub days: Option<Vec<u32>>,
+    /// Also run parts that take minutes; --slow=false skips them
+    #[arg(long, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    pub slow: Option<bool>,
+    /// Load solver plugins from DIR
+    #[arg(long, global = true, value_name = "DIR")]
+    pub plugin_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads `aoc.toml` from the workspace, or the file named on the
+    /// command line, then applies the command line settings on top.
+    pub fn load(overrides: Overrides) -> Result<Config, String> {
+        let path = overrides.config.clone()
+            .unwrap_or_else(|| registry::workspace_dir().join("aoc.toml"));
+
+        let mut config = match std::fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text, path.parent().unwrap_or(Path::new(".")))
+                .map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(_) if overrides.config.is_none() => Config::default(),
+            Err(e) => return Err(format!("unable to read {}: {e}", path.display())),
+        };
+        config.apply(overrides);
+        Ok(config)
+    }
+
+    /// Parses a config file, resolving relative paths against `base`.
+    pub fn parse(text: &str, base: &Path) -> Result<Config, String> {
+        let mut config: Config = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        config.input_dir = config.input_dir.map(|p| base.join(p));
+        config.viz_dir = config.viz_dir.map(|p| base.join(p));
+        config.plugin_dir = config.plugin_dir.map(|p| base.join(p));
+        Ok(config)
+    }
+
+    pub fn apply(&mut self, o: Overrides) {
+        self.year = o.year.or(self.year);
+        self.input_dir = o.input_dir.or(self.input_dir.take());
+        self.account = o.account.or(self.account.take());
+        self.format = o.format.unwrap_or(self.format);
+        self.timeout = o.timeout.or(self.timeout);
+        self.viz_dir = o.viz_dir.or(self.viz_dir.take());
+        self.days = o.days.or(self.days.take());
+        self.slow = o.slow.unwrap_or(self.slow);
+        self.plugin_dir = o.plugin_dir.or(self.plugin_dir.take());
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year.unwrap_or_else(registry::latest_year)
+    }
+
+    pub fn enabled(&self, day: u32) -> bool {
+        self.days.as_ref().is_none_or(|days| days.contains(&day))
+    }
+
+    /// Every registered day of the year that is enabled.
+    pub fn days(&self) -> Vec<&'static Day> {
+        registry::DAYS.iter().filter(|d| d.year == self.year() && self.enabled(d.day)).collect()
+    }
+
+    /// An enabled, registered day of the year.
+    pub fn find(&self, day: u32) -> Result<&'static Day, String> {
+        let year = self.year();
+        match registry::find(year, day) {
+            Some(d) if self.enabled(day) => Ok(d),
+            Some(_) => Err(format!("day {day} is not enabled")),
+            None => Err(format!("day {day} of {year} is not registered")),
+        }
+    }
+
+    pub fn input_path(&self, day: &Day) -> PathBuf {
+        match &self.input_dir {
+            Some(dir) => dir.join(day.year.to_string()).join(format!("day-{:02}.txt", day.day)),
+            None => day.input_path(),
+        }
+    }
+
+    /// Whether `part` of `day` should be solved.
+    pub fn runs(&self, day: &Day, part: u32) -> bool {
+        self.slow || !day.slow.contains(&part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file() {
+        let text = r#"
+            year = 2023
+            input_dir = "inputs"
+            account = "work"
+            format = "json"
+            timeout = 30
+            viz_dir = "/tmp/viz"
+            days = [1, 2, 10]
+            slow = true
+            plugin_dir = "plugins"
+        "#;
+        let config = Config::parse(text, Path::new("/ws")).unwrap();
+
+        assert_eq!(config, Config {
+            year: Some(2023),
+            input_dir: Some(PathBuf::from("/ws/inputs")),
+            account: Some("work".to_string()),
+            format: Format::Json,
+            timeout: Some(30),
+            viz_dir: Some(PathBuf::from("/tmp/viz")),
+            days: Some(vec![1, 2, 10]),
+            slow: true,
+            plugin_dir: Some(PathBuf::from("/ws/plugins")),
+        });
+        assert_eq!(Config::parse("", Path::new("/ws")).unwrap(), Config::default());
+        assert!(Config::parse("colour = true", Path::new("/ws")).is_err());
+        assert!(Config::parse("format = \"xml\"", Path::new("/ws")).is_err());
+    }
+
+    #[test]
+    fn command_line_wins() {
+        let mut config = Config::parse("account = \"work\"\ntimeout = 30\ndays = [1]\nslow = true", Path::new("/ws")).unwrap();
+        config.apply(Overrides {
+            timeout: Some(5),
+            days: Some(vec![4, 5]),
+            format: Some(Format::Json),
+            slow: Some(false),
+            ..Overrides::default()
+        });
+
+        assert_eq!(config.account.as_deref(), Some("work"));
+        assert_eq!(config.timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(config.format, Format::Json);
+        assert!(!config.slow);
+        assert_eq!(config.days().iter().map(|d| d.day).collect::<Vec<_>>(), vec![4, 5]);
+    }
+
+    #[test]
+    fn days_and_inputs() {
+        let config = Config { days: Some(vec![5, 10]), input_dir: Some(PathBuf::from("in")), ..Config::default() };
+        let d5 = config.find(5).unwrap();
+
+        assert!(config.find(4).is_err());
+        assert!(config.find(26).is_err());
+        assert!(Config { year: Some(2022), ..Config::default() }.find(5).is_err());
+        assert_eq!(config.year(), 2023);
+        assert_eq!(config.input_path(d5), PathBuf::from("in/2023/day-05.txt"));
+        assert!(config.runs(d5, 1) && config.runs(d5, 2));
+        assert_eq!(Config::default().input_path(d5), d5.input_path());
+    }
+
+    #[test]
+    fn slow_parts() {
+        static SLOW: Day = Day {
+            year: 2023,
+            day: 9,
+            title: "Mirage Maintenance",
+            parts: [day_09::solve_part1, day_09::solve_part2],
+            slow: &[2],
+            viz: None,
+            long: &[],
+            variants: &[],
+            stream: &[],
+        };
+
+        assert!(Config::default().runs(&SLOW, 1));
+        assert!(!Config::default().runs(&SLOW, 2));
+        assert!(Config { slow: true, ..Config::default() }.runs(&SLOW, 2));
+    }
+}
@@ -0,0 +1,442 @@
+//! `aoc dashboard`: a grid of the enabled days showing what is known about
+//! each, from which the selected day can be run, tested or visualized.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use shared::cancel::Cancel;
+use crate::check;
+use crate::config::Config;
+use crate::perf;
+use crate::registry::Day;
+use crate::run::{self, PartRun, Status};
+use crate::status::{self, Answer, Tests};
+
+const CELL_WIDTH: u16 = 24;
+const CELL_HEIGHT: u16 = 6;
+const LOG_LINES: u16 = 5;
+const HELP: &str = "←↓↑→ move  r run  t test  T test all  v visualize  q quit";
+
+/// What the dashboard shows for one day.
+pub struct Cell {
+    pub day: &'static Day,
+    pub answers: [Answer; 2],
+    /// Each part's latest time, from the perf history or a run here.
+    pub times: [Option<Duration>; 2],
+    /// How the tests went, once they have run.
+    pub tests: Option<Tests>,
+    /// Sample inputs, `input2.txt` and on.
+    pub samples: usize,
+    /// What is being done with the day in the background.
+    pub busy: Option<&'static str>,
+}
+
+impl Cell {
+    pub fn new(day: &'static Day) -> Self {
+        Cell {
+            day,
+            answers: [Answer::Unsolved(0), Answer::Unsolved(0)],
+            times: [None, None],
+            tests: None,
+            samples: 0,
+            busy: None,
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let stars = self.answers.iter()
+            .map(|a| match a.is_accepted() {
+                true => Span::styled("★ ", Style::new().fg(Color::Yellow)),
+                false => Span::styled("☆ ", Style::new().fg(Color::DarkGray)),
+            })
+            .collect::<Vec<_>>();
+
+        let times = self.times.iter()
+            .map(|t| t.map_or("-".to_string(), |t| format!("{t:.1?}")))
+            .collect::<Vec<_>>()
+            .join(" / ");
+
+        let tests = match &self.tests {
+            None => Span::styled("tests not run", Style::new().fg(Color::DarkGray)),
+            Some(t @ Tests::Ran { passed, .. }) if t.is_ok() => Span::styled(format!("tests ok, {passed}"), Style::new().fg(Color::Green)),
+            Some(Tests::Ran { failed, .. }) => Span::styled(format!("tests FAILED, {failed}"), Style::new().fg(Color::Red)),
+            Some(Tests::Broken(why)) => Span::styled(why.clone(), Style::new().fg(Color::Red)),
+        };
+
+        let samples = match self.samples {
+            0 => "no samples".to_string(),
+            1 => "1 sample".to_string(),
+            n => format!("{n} samples"),
+        };
+
+        vec![Line::from(stars), Line::from(times), Line::from(tests), Line::from(samples)]
+    }
+}
+
+/// Something to do, asked for with a key.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Run(usize),
+    Test(usize),
+    TestAll,
+    Visualize(usize),
+    Quit,
+}
+
+// Work done off the UI thread, sent back once finished
+enum Done {
+    Ran(usize, Result<Vec<PartRun>, String>),
+    Tested(usize, Tests),
+    Drew(usize, Result<String, String>),
+}
+
+// A text shown over the whole screen, such as a visualization
+struct View {
+    title: String,
+    text: String,
+    scroll: u16,
+}
+
+pub struct App {
+    pub cells: Vec<Cell>,
+    pub selected: usize,
+    /// Cells per row, as last drawn.
+    pub cols: usize,
+    log: Vec<String>,
+    view: Option<View>,
+}
+
+impl App {
+    pub fn new(cells: Vec<Cell>) -> Self {
+        App { cells, selected: 0, cols: 1, log: vec![], view: None }
+    }
+
+    /// Moves the selection, or says what to do.
+    pub fn on_key(&mut self, key: KeyCode) -> Option<Action> {
+        if let Some(view) = &mut self.view {
+            match key {
+                KeyCode::Up | KeyCode::Char('k') => view.scroll = view.scroll.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => view.scroll = view.scroll.saturating_add(1),
+                KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(20),
+                KeyCode::PageDown => view.scroll = view.scroll.saturating_add(20),
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => self.view = None,
+                _ => {}
+            }
+            return None;
+        }
+
+        let last = self.cells.len().saturating_sub(1);
+        match key {
+            KeyCode::Left | KeyCode::Char('h') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') if self.selected >= self.cols => self.selected -= self.cols,
+            KeyCode::Down | KeyCode::Char('j') if self.selected + self.cols <= last => self.selected += self.cols,
+            KeyCode::Esc | KeyCode::Char('q') => return Some(Action::Quit),
+            _ if self.cells.is_empty() => {}
+            KeyCode::Char('r') => return Some(Action::Run(self.selected)),
+            KeyCode::Char('t') => return Some(Action::Test(self.selected)),
+            KeyCode::Char('T') => return Some(Action::TestAll),
+            KeyCode::Char('v') => return Some(Action::Visualize(self.selected)),
+            _ => {}
+        }
+        None
+    }
+
+    fn say(&mut self, line: String) {
+        self.log.push(line);
+    }
+
+    // Marks the cell busy, unless it already is
+    fn claim(&mut self, i: usize, what: &'static str) -> bool {
+        let cell = &mut self.cells[i];
+        match cell.busy {
+            Some(busy) => {
+                let line = format!("day {:>2}: still {busy}", cell.day.day);
+                self.say(line);
+                false
+            }
+            None => {
+                cell.busy = Some(what);
+                true
+            }
+        }
+    }
+
+    fn finish(&mut self, done: Done) {
+        match done {
+            Done::Ran(i, Ok(runs)) => {
+                for r in runs {
+                    if r.status == Status::Solved {
+                        self.cells[i].times[r.part as usize - 1] = Some(r.elapsed);
+                    }
+                    self.say(r.to_string());
+                }
+                self.cells[i].busy = None;
+            }
+            Done::Tested(i, tests) => {
+                self.say(format!("day {:>2} tests: {tests}", self.cells[i].day.day));
+                self.cells[i].tests = Some(tests);
+                self.cells[i].busy = None;
+            }
+            Done::Drew(i, Ok(text)) => {
+                let d = self.cells[i].day;
+                self.view = Some(View { title: format!(" Day {}: {} ", d.day, d.title), text, scroll: 0 });
+                self.cells[i].busy = None;
+            }
+            Done::Ran(i, Err(e)) | Done::Drew(i, Err(e)) => {
+                self.say(format!("day {:>2}: {e}", self.cells[i].day.day));
+                self.cells[i].busy = None;
+            }
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        if let Some(view) = &self.view {
+            let block = Block::bordered()
+                .title(view.title.clone())
+                .title_bottom(" ↑↓ scroll  esc back ");
+            frame.render_widget(Paragraph::new(view.text.as_str()).block(block).scroll((view.scroll, 0)), frame.area());
+            return;
+        }
+
+        let [header, grid, log, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(CELL_HEIGHT),
+            Constraint::Length(LOG_LINES + 2),
+            Constraint::Length(1),
+        ]).areas(frame.area());
+
+        let stars = self.cells.iter().flat_map(|c| &c.answers).filter(|a| a.is_accepted()).count();
+        let year = self.cells.first().map_or(String::new(), |c| c.day.year.to_string());
+        frame.render_widget(Line::from(format!("Advent of Code {year}  {stars} ★")).bold(), header);
+
+        self.draw_grid(frame, grid);
+
+        let lines = self.log.iter()
+            .skip(self.log.len().saturating_sub(LOG_LINES as usize))
+            .map(|l| Line::from(l.as_str()))
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Log ")), log);
+        frame.render_widget(Line::from(HELP).dark_gray(), help);
+    }
+
+    fn draw_grid(&mut self, frame: &mut Frame, area: Rect) {
+        self.cols = (area.width / CELL_WIDTH).max(1) as usize;
+        let rows = (area.height / CELL_HEIGHT).max(1) as usize;
+        // Scroll just far enough to keep the selection in view
+        let first = (self.selected / self.cols).saturating_sub(rows - 1) * self.cols;
+
+        for (n, cell) in self.cells.iter().enumerate().skip(first).take(rows * self.cols) {
+            let (row, col) = ((n - first) / self.cols, (n - first) % self.cols);
+            let rect = Rect {
+                x: area.x + col as u16 * CELL_WIDTH,
+                y: area.y + row as u16 * CELL_HEIGHT,
+                width: CELL_WIDTH,
+                height: CELL_HEIGHT,
+            }.intersection(area);
+
+            let mut block = Block::bordered()
+                .title(format!(" {} {} ", cell.day.day, cell.day.title))
+                .title_bottom(cell.busy.map(|b| format!(" {b}… ")).unwrap_or_default());
+            if n == self.selected {
+                block = block.border_style(Style::new().fg(Color::Yellow).bold());
+            }
+            frame.render_widget(Paragraph::new(cell.lines()).block(block), rect);
+        }
+    }
+}
+
+/// Reads what is known about each enabled day, without running anything.
+pub fn load(config: &Config, cache: &Path) -> Vec<Cell> {
+    let ledgers = status::ledgers(cache);
+    let latest = perf::load(&perf::history_path())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|s| Some(((s.year, s.day, s.part), Duration::from_nanos(s.nanos?))))
+        .collect::<HashMap<_, _>>();
+
+    config.days().into_iter()
+        .map(|d| Cell {
+            answers: [1, 2].map(|p| status::answer(&ledgers, d, p)),
+            times: [1, 2].map(|p| latest.get(&(d.year, d.day, p)).copied()),
+            samples: check::cases(d, 0).len(),
+            ..Cell::new(d)
+        })
+        .collect()
+}
+
+/// Shows the dashboard until it is quit, testing every day first unless
+/// `no_tests` is set.
+pub fn show(config: &Config, cache: &Path, no_tests: bool) -> io::Result<()> {
+    let mut app = App::new(load(config, cache));
+    let (tx, rx) = mpsc::channel();
+    if !no_tests {
+        start(&mut app, config, Action::TestAll, &tx);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, config, &tx, &rx);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    config: &Config,
+    tx: &Sender<Done>,
+    rx: &Receiver<Done>,
+) -> io::Result<()> {
+    loop {
+        while let Ok(done) = rx.try_recv() {
+            app.finish(done);
+        }
+        terminal.draw(|f| app.draw(f))?;
+
+        // Wake up now and then to pick up finished work
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match app.on_key(key.code) {
+            Some(Action::Quit) => return Ok(()),
+            Some(action) => start(app, config, action, tx),
+            None => {}
+        }
+    }
+}
+
+// Starts the work on its own thread, which sends a `Done` when finished
+fn start(app: &mut App, config: &Config, action: Action, tx: &Sender<Done>) {
+    let tx = tx.clone();
+    match action {
+        Action::Run(i) => {
+            let d = app.cells[i].day;
+            let parts = (1..=2).filter(|&p| config.runs(d, p)).collect::<Vec<_>>();
+            if parts.len() < 2 {
+                app.say(format!("day {:>2}: skipping slow parts (run with --slow)", d.day));
+            }
+            if !app.claim(i, "running") {
+                return;
+            }
+            let (path, timeout) = (config.input_path(d), config.timeout());
+            thread::spawn(move || {
+                let runs = shared::input::read(&path)
+                    .map(|text| parts.into_iter()
+                        .filter_map(|p| run::run_part_within(d, p, None, &text, timeout, Cancel::default()))
+                        .collect())
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(Done::Ran(i, runs));
+            });
+        }
+        Action::Test(i) => {
+            if !app.claim(i, "testing") {
+                return;
+            }
+            let d = app.cells[i].day;
+            thread::spawn(move || {
+                let _ = tx.send(Done::Tested(i, status::run_tests(d)));
+            });
+        }
+        Action::TestAll => {
+            // One at a time, since cargo would only wait on its build lock
+            let mut days = vec![];
+            for i in 0..app.cells.len() {
+                if app.claim(i, "testing") {
+                    days.push((i, app.cells[i].day));
+                }
+            }
+            thread::spawn(move || {
+                for (i, d) in days {
+                    let _ = tx.send(Done::Tested(i, status::run_tests(d)));
+                }
+            });
+        }
+        Action::Visualize(i) => {
+            let d = app.cells[i].day;
+            let Some(viz) = d.viz else {
+                app.say(format!("day {:>2}: no visualization", d.day));
+                return;
+            };
+            if !app.claim(i, "drawing") {
+                return;
+            }
+            let path = config.input_path(d);
+            thread::spawn(move || {
+                let text = shared::input::read(&path).map(|text| viz(&text)).map_err(|e| e.to_string());
+                let _ = tx.send(Done::Drew(i, text));
+            });
+        }
+        Action::Quit => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use crate::registry::DAYS;
+
+    fn app() -> App {
+        App::new(DAYS.iter().filter(|d| d.year == 2023).map(Cell::new).collect())
+    }
+
+    #[test]
+    fn move_and_act() {
+        let mut app = app();
+        app.cols = 4;
+
+        assert_eq!(app.on_key(KeyCode::Left), None);
+        assert_eq!(app.selected, 0);
+        app.on_key(KeyCode::Down);
+        app.on_key(KeyCode::Char('l'));
+        assert_eq!(app.selected, 5);
+        assert_eq!(app.on_key(KeyCode::Char('r')), Some(Action::Run(5)));
+
+        app.on_key(KeyCode::Down);
+        app.on_key(KeyCode::Down);
+        assert_eq!(app.selected, 9);
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Right);
+        assert_eq!(app.selected, app.cells.len() - 1);
+        app.on_key(KeyCode::Char('k'));
+        assert_eq!(app.on_key(KeyCode::Char('t')), Some(Action::Test(app.cells.len() - 5)));
+        assert_eq!(app.on_key(KeyCode::Char('q')), Some(Action::Quit));
+    }
+
+    #[test]
+    fn draw_cells() {
+        let mut app = app();
+        app.cells[0].answers = [Answer::Accepted("142".to_string()), Answer::Unsolved(2)];
+        app.cells[0].times = [Some(Duration::from_micros(1500)), None];
+        app.cells[0].tests = Some(Tests::Ran { passed: 3, failed: 0 });
+        app.cells[0].samples = 2;
+        app.cells[1].busy = Some("testing");
+        app.say("day  1 part 1: 142".to_string());
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = terminal.backend().buffer().content().chunks(100)
+            .map(|row| row.iter().map(|c| c.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(app.cols, 4);
+        for text in ["Advent of Code 2023  1 ★", " 1 Trebuchet?! ", "★ ☆", "1.5ms / -", "tests ok, 3", "2 samples", " testing… ", "day  1 part 1: 142"] {
+            assert!(screen.contains(text), "no {text:?} in\n{screen}");
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! A profile of a puzzle input, for sizing up a new day's format: its
+//! shape, the characters and numbers in it, and how it is split up.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How the lines of an input end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineEndings {
+    None,
+    Lf,
+    CrLf,
+    Mixed,
+}
+
+/// The numbers in an input, read as runs of digits with an optional `-`
+/// that does not follow a letter or digit, as in `-3` but not `day-05`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Numbers {
+    pub count: usize,
+    pub min: i128,
+    pub max: i128,
+    /// The most digits in any one number.
+    pub digits: usize,
+    /// Numbers too big even for an `i128`, left out of the range.
+    pub overflowed: usize,
+}
+
+impl Numbers {
+    /// The smallest integer type that holds every number.
+    pub fn fits(&self) -> &'static str {
+        let fits = |min: i128, max: i128| self.overflowed == 0 && self.min >= min && self.max <= max;
+        if fits(i32::MIN.into(), i32::MAX.into()) {
+            "i32"
+        } else if fits(i64::MIN.into(), i64::MAX.into()) {
+            "i64"
+        } else if self.overflowed == 0 {
+            "i128"
+        } else {
+            "nothing built in"
+        }
+    }
+}
+
+/// A run of lines between blank ones, numbered from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub first: usize,
+    pub last: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub bytes: usize,
+    pub lines: usize,
+    pub endings: LineEndings,
+    /// Whether the last line ends in a newline.
+    pub trailing_newline: bool,
+    /// How many lines have each width, in characters, blank ones included.
+    pub widths: BTreeMap<usize, usize>,
+    /// The lines, by number, whose width differs from the usual one.
+    pub ragged: Vec<usize>,
+    pub chars: BTreeMap<char, usize>,
+    pub numbers: Option<Numbers>,
+    pub blocks: Vec<Block>,
+}
+
+impl Profile {
+    pub fn new(text: &str) -> Self {
+        let mut lines = text.split('\n').collect::<Vec<_>>();
+        let trailing_newline = text.ends_with('\n');
+        if trailing_newline || text.is_empty() {
+            lines.pop();
+        }
+
+        let crlf = lines.iter().filter(|l| l.ends_with('\r')).count();
+        let newlines = lines.len() - usize::from(!trailing_newline && !lines.is_empty());
+        let endings = match (newlines, crlf) {
+            (0, _) => LineEndings::None,
+            (_, 0) => LineEndings::Lf,
+            (n, c) if c == n => LineEndings::CrLf,
+            _ => LineEndings::Mixed,
+        };
+        let lines = lines.into_iter().map(|l| l.strip_suffix('\r').unwrap_or(l)).collect::<Vec<_>>();
+
+        let mut widths = BTreeMap::new();
+        for l in &lines {
+            *widths.entry(l.chars().count()).or_default() += 1;
+        }
+        // The width most non-blank lines have, the narrower on a tie
+        let usual = widths.iter()
+            .filter(|(w, _)| **w > 0)
+            .max_by_key(|(w, n)| (**n, std::cmp::Reverse(**w)))
+            .map(|(w, _)| *w);
+        let ragged = lines.iter()
+            .enumerate()
+            .filter(|(_, l)| !l.is_empty() && Some(l.chars().count()) != usual)
+            .map(|(i, _)| i + 1)
+            .collect();
+
+        let mut chars = BTreeMap::new();
+        for c in lines.iter().flat_map(|l| l.chars()) {
+            *chars.entry(c).or_default() += 1;
+        }
+
+        let mut blocks: Vec<Block> = vec![];
+        for (i, l) in lines.iter().enumerate() {
+            match blocks.last_mut() {
+                _ if l.trim().is_empty() => continue,
+                Some(b) if b.last == i => b.last = i + 1,
+                _ => blocks.push(Block { first: i + 1, last: i + 1 }),
+            }
+        }
+
+        Profile {
+            bytes: text.len(),
+            lines: lines.len(),
+            endings,
+            trailing_newline,
+            widths,
+            ragged,
+            chars,
+            numbers: numbers(&lines),
+            blocks,
+        }
+    }
+
+    /// Whether every line is as wide as the rest, and there is more than one.
+    pub fn is_grid(&self) -> bool {
+        self.lines > 1 && self.widths.len() == 1 && !self.widths.contains_key(&0)
+    }
+}
+
+fn numbers(lines: &[&str]) -> Option<Numbers> {
+    let mut found: Option<Numbers> = None;
+    for line in lines {
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let end = bytes[i..].iter().position(|b| !b.is_ascii_digit()).map_or(bytes.len(), |n| i + n);
+            let signed = i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_alphanumeric());
+            let start = if signed { i - 1 } else { i };
+
+            let n = found.get_or_insert(Numbers { count: 0, min: i128::MAX, max: i128::MIN, digits: 0, overflowed: 0 });
+            n.count += 1;
+            n.digits = n.digits.max(end - i);
+            match line[start..end].parse::<i128>() {
+                Ok(v) => {
+                    n.min = n.min.min(v);
+                    n.max = n.max.max(v);
+                }
+                Err(_) => n.overflowed += 1,
+            }
+            i = end;
+        }
+    }
+    found
+}
+
+fn lines(n: usize) -> String {
+    match n {
+        1 => "1 line".to_string(),
+        n => format!("{n} lines"),
+    }
+}
+
+// Whitespace and control characters, as they would be written in Rust
+fn show_char(c: char) -> String {
+    match c {
+        ' ' => "' '".to_string(),
+        c if c.is_whitespace() || c.is_control() => format!("{:?}", c),
+        c => c.to_string(),
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let endings = match self.endings {
+            LineEndings::None => "none",
+            LineEndings::Lf => "LF",
+            LineEndings::CrLf => "CRLF",
+            LineEndings::Mixed => "mixed LF and CRLF",
+        };
+        let trailing = if self.trailing_newline { "" } else { ", no newline at the end" };
+        writeln!(f, "{} bytes, {} lines, line endings {endings}{trailing}", self.bytes, self.lines)?;
+
+        match (self.is_grid(), self.widths.keys().next(), self.widths.keys().last()) {
+            (true, Some(w), _) => writeln!(f, "grid of {w} x {}", self.lines)?,
+            (false, Some(min), Some(max)) => writeln!(f, "not a grid, widths {min} to {max}")?,
+            _ => writeln!(f, "empty")?,
+        }
+        if self.widths.len() > 1 {
+            writeln!(f, "\nwidths:")?;
+            for (w, n) in &self.widths {
+                writeln!(f, "  {w:>6}: {}", lines(*n))?;
+            }
+        }
+        if !self.ragged.is_empty() {
+            let shown = self.ragged.iter().take(10).map(|l| l.to_string()).collect::<Vec<_>>();
+            let more = if self.ragged.len() > shown.len() { ", ..." } else { "" };
+            writeln!(f, "ragged lines: {} ({}{more})", self.ragged.len(), shown.join(", "))?;
+        }
+
+        if !self.chars.is_empty() {
+            writeln!(f, "\ncharacters:")?;
+            let mut chars = self.chars.iter().collect::<Vec<_>>();
+            chars.sort_by_key(|(c, n)| (std::cmp::Reverse(**n), **c));
+            for (c, n) in chars {
+                writeln!(f, "  {:>6}: {n}", show_char(*c))?;
+            }
+        }
+
+        match &self.numbers {
+            Some(n) if n.overflowed == n.count => {
+                writeln!(f, "\nnumbers: {}, up to {} digits, too big for an i128", n.count, n.digits)?
+            }
+            Some(n) => {
+                let overflowed = match n.overflowed {
+                    0 => String::new(),
+                    o => format!(", {o} too big for an i128"),
+                };
+                writeln!(f, "\nnumbers: {}, {} to {}, up to {} digits{overflowed}", n.count, n.min, n.max, n.digits)?;
+                writeln!(f, "  each fits in {}", n.fits())?;
+            }
+            None => writeln!(f, "\nnumbers: none")?,
+        }
+
+        if self.blocks.len() > 1 {
+            writeln!(f, "\n{} blocks between blank lines:", self.blocks.len())?;
+            for b in &self.blocks {
+                writeln!(f, "  lines {}-{} ({})", b.first, b.last, lines(b.last - b.first + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_blocks_and_numbers() {
+        let text = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n-3 4000000000 1\r\n";
+        let p = Profile::new(text);
+
+        assert_eq!((p.lines, p.endings, p.trailing_newline), (5, LineEndings::CrLf, true));
+        assert_eq!(p.widths, BTreeMap::from([(0, 1), (7, 1), (12, 1), (15, 1), (17, 1)]));
+        assert_eq!(p.ragged, vec![1, 3, 5]);
+        assert_eq!(p.blocks, vec![Block { first: 1, last: 1 }, Block { first: 3, last: 5 }]);
+        assert_eq!(p.chars[&'9'], 2);
+        assert!(!p.is_grid());
+
+        let n = p.numbers.unwrap();
+        assert_eq!((n.count, n.min, n.max, n.digits), (8, -3, 4000000000, 10));
+        assert_eq!(n.fits(), "i64");
+    }
+
+    #[test]
+    fn profile_grid() {
+        let p = Profile::new("..F-.\n.FJ|.\nSJ.L-");
+        assert!(p.is_grid());
+        assert_eq!((p.endings, p.trailing_newline, &p.numbers), (LineEndings::Lf, false, &None));
+
+        let report = p.to_string();
+        assert!(report.starts_with("17 bytes, 3 lines, line endings LF, no newline at the end\ngrid of 5 x 3\n"));
+        assert!(report.contains("\n       .: 6\n"));
+        assert_eq!(Profile::new("12").endings, LineEndings::None);
+        assert_eq!(Profile::new("").lines, 0);
+    }
+}
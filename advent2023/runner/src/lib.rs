@@ -0,0 +1,14 @@
+//! The solver registry and the ways of running it, shared by the `aoc`
+//! command line and the `server` binary.
+
+pub mod api;
+pub mod check;
+pub mod config;
+pub mod dashboard;
+pub mod inspect;
+pub mod perf;
+pub mod plugin;
+pub mod registry;
+pub mod run;
+pub mod show;
+pub mod status;
@@ -0,0 +1,596 @@
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use clap::{Parser, Subcommand};
+use miette::{miette, IntoDiagnostic, Result};
+use runner::config::{Config, Format, Overrides};
+use runner::{check, dashboard, inspect, perf, plugin, registry, run, show, status};
+use shared::cancel::Cancel;
+use shared::fetch::{Cache, Fetcher, HttpClient};
+use shared::input::{self, Key};
+use shared::submit::{Guess, Ledger, Submitter, Verdict};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: shared::alloc::CountingAlloc = shared::alloc::CountingAlloc;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    overrides: Overrides,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle
+    Run {
+        #[arg(required_unless_present = "all")]
+        day: Option<u32>,
+        /// Only solve this part
+        part: Option<u32>,
+        /// Read the puzzle input from here instead of the day's input1.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Solve every enabled day
+        #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+        all: bool,
+        /// Worker threads for --all; defaults to one per core
+        #[arg(long, conflicts_with = "day")]
+        jobs: Option<usize>,
+        /// Use this implementation, for the parts that have one by that name
+        #[arg(long, conflicts_with = "all")]
+        variant: Option<String>,
+        /// Read the input a line at a time instead of loading it, for parts
+        /// that can be solved that way
+        #[arg(long, conflicts_with_all = ["all", "variant"])]
+        stream: bool,
+    },
+    /// Check that every implementation of a part gives the same answers,
+    /// on the samples and on generated inputs
+    Check {
+        /// Only check this day, instead of every enabled day
+        day: Option<u32>,
+        /// Generated inputs to try
+        #[arg(long, default_value_t = 20)]
+        cases: usize,
+    },
+    /// Browse every enabled day's stars, times, tests and samples, and
+    /// run, test or visualize them from there
+    Dashboard {
+        /// Skip running every day's tests on the way in
+        #[arg(long)]
+        no_tests: bool,
+    },
+    /// Download a day's input
+    Fetch {
+        day: u32,
+        /// Replace an existing input
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt a day's input, or every enabled day's, so it can be committed
+    Encrypt {
+        day: Option<u32>,
+    },
+    /// Profile an input file: its shape, characters, numbers and blocks
+    Inspect {
+        file: PathBuf,
+    },
+    /// Read a day's puzzle description
+    Show {
+        day: u32,
+        /// Skip running the day's tests
+        #[arg(long)]
+        no_tests: bool,
+        /// Print straight to stdout instead of through $PAGER
+        #[arg(long)]
+        no_pager: bool,
+    },
+    /// Solve a part and send the answer to adventofcode.com
+    Submit {
+        day: u32,
+        part: u32,
+        /// Send this instead of solving
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Time each part over several runs and record it in the perf history.
+    /// A `count-alloc` build records allocation counts instead of times
+    Bench {
+        /// Only benchmark this day, instead of every enabled day
+        day: Option<u32>,
+        /// Runs per part; the median is kept
+        #[arg(long, default_value_t = 5)]
+        samples: usize,
+        /// Print the results without recording them
+        #[arg(long)]
+        no_record: bool,
+        /// History file to append to
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Inspect the recorded perf history
+    Perf {
+        #[command(subcommand)]
+        command: PerfCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PerfCommand {
+    /// Flag parts that got slower, or allocate more, than at a baseline commit
+    Diff {
+        /// Baseline commit, or a unique prefix of one
+        base: String,
+        /// Commit to check; defaults to the most recently recorded one
+        head: Option<String>,
+        /// Percentage growth allowed before a part is flagged
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// History file to read
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = Config::load(cli.overrides).map_err(|e| miette!("{e}"))?;
+    if let Some(dir) = &config.plugin_dir {
+        load_plugins(dir)?;
+    }
+
+    match cli.command {
+        Command::Run { all: true, jobs, .. } => run_all(&config, jobs),
+        Command::Run { day, part, input, stream: true, .. } => {
+            run_streamed(&config, day.unwrap_or_default(), part, input)
+        }
+        Command::Run { day, part, input, variant, .. } => {
+            run(&config, day.unwrap_or_default(), part, input, variant.as_deref())
+        }
+        Command::Check { day, cases } => check(&config, day, cases),
+        Command::Dashboard { no_tests } => dashboard::show(&config, &cache_dir(), no_tests).into_diagnostic(),
+        Command::Fetch { day, force } => fetch(&config, day, force),
+        Command::Encrypt { day } => encrypt(&config, day),
+        Command::Inspect { file } => inspect(&file),
+        Command::Show { day, no_tests, no_pager } => show(&config, day, no_tests, no_pager),
+        Command::Submit { day, part, answer } => submit(&config, day, part, answer),
+        Command::Bench { day, samples, no_record, history } => bench(&config, day, samples, no_record, history),
+        Command::Perf { command: PerfCommand::Diff { base, head, threshold, history } } => {
+            perf_diff(&base, head.as_deref(), threshold, history)
+        }
+    }
+}
+
+// Loads the plugins in `dir`, saying which of the days' own solvers they
+// take over from, since that changes answers without a --variant
+fn load_plugins(dir: &Path) -> Result<()> {
+    for e in plugin::load_dir(dir).map_err(|e| miette!("{e}"))? {
+        let shadows = registry::find(e.year, e.day)
+            .is_some_and(|d| e.variant == registry::DEFAULT_VARIANT || d.variants.iter().any(|v| (v.part, v.name) == (e.part, e.variant)));
+        if shadows {
+            eprintln!("day {} part {} ({}) is solved by {}", e.day, e.part, e.variant, e.plugin.path.display());
+        }
+    }
+    Ok(())
+}
+
+fn run(config: &Config, day: u32, part: Option<u32>, input: Option<PathBuf>, variant: Option<&str>) -> Result<()> {
+    let d = find(config, day)?;
+
+    // Slow parts still run when asked for by number, or by variant
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let variants = parts.iter()
+        .map(|&p| {
+            let name = variant?;
+            d.variants(p).into_iter().map(|(n, _)| n).find(|n| *n == name)
+        })
+        .collect::<Vec<_>>();
+    if let Some(name) = variant.filter(|_| variants.iter().all(Option::is_none)) {
+        let mut known = parts.iter()
+            .flat_map(|&p| d.variants(p))
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>();
+        known.dedup();
+        return Err(miette!("day {day} has no variant {name}, only {}", known.join(", ")));
+    }
+
+    let text = read_input(&input.unwrap_or_else(|| config.input_path(d)))?;
+
+    if config.format == Format::Text {
+        println!("--- Day {}: {} ---", d.day, d.title);
+    }
+    for (p, v) in parts.into_iter().zip(variants) {
+        if part.is_none() && v.is_none() && !config.runs(d, p) {
+            print_skipped(config, d, p);
+            continue;
+        }
+        let r = run::run_part_within(d, p, v, &text, config.timeout(), progress(config, d, p, v))
+            .ok_or_else(|| miette!("day {day} has no part {p}"))?;
+        if shows_progress(config, d, p, v) {
+            eprint!("\r\x1b[K");
+        }
+        print_part(config, &r);
+    }
+    draw(config, d, &text)
+}
+
+// Solves from the input file a line at a time, for inputs too big to load
+fn run_streamed(config: &Config, day: u32, part: Option<u32>, input: Option<PathBuf>) -> Result<()> {
+    let d = find(config, day)?;
+    let path = input.unwrap_or_else(|| config.input_path(d));
+
+    if config.format == Format::Text {
+        println!("--- Day {}: {} ---", d.day, d.title);
+    }
+    for p in part.map_or(vec![1, 2], |p| vec![p]) {
+        let Some(r) = run::stream_part(d, p, &path) else {
+            if part.is_some() {
+                return Err(miette!("day {day} part {p} can not be streamed"));
+            }
+            if config.format == Format::Text {
+                println!("day {day:>2} part {p}: skipped, can not be streamed");
+            }
+            continue;
+        };
+        let r = r.into_diagnostic().map_err(|e| e.wrap_err(format!("unable to solve from {}", path.display())))?;
+        print_part(config, &r);
+    }
+    Ok(())
+}
+
+fn run_all(config: &Config, jobs: Option<usize>) -> Result<()> {
+    let jobs = jobs
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1);
+    let days = config.days();
+
+    let start = Instant::now();
+    let runs = run::run_days(&days, jobs, config, |d| read_input(&config.input_path(d)));
+    let wall = start.elapsed();
+
+    let mut cpu = Duration::ZERO;
+    for r in runs {
+        let r = r?;
+        if config.format == Format::Text {
+            println!("--- Day {}: {} ({:.2?} cpu) ---", r.day.day, r.day.title, r.cpu);
+        }
+        for p in 1..=2 {
+            match r.parts.iter().find(|x| x.part == p) {
+                Some(x) => print_part(config, x),
+                None => print_skipped(config, r.day, p),
+            }
+        }
+        if config.viz_dir.is_some() {
+            draw(config, r.day, &read_input(&config.input_path(r.day))?)?;
+        }
+        cpu += r.cpu;
+    }
+
+    if config.format == Format::Text {
+        println!("{} days on {jobs} jobs: {wall:.2?} wall, {cpu:.2?} cpu", days.len());
+    }
+    Ok(())
+}
+
+fn check(config: &Config, day: Option<u32>, cases: usize) -> Result<()> {
+    let days = match day {
+        Some(n) => vec![find(config, n)?],
+        None => config.days(),
+    };
+    let budget = config.timeout().unwrap_or(Duration::from_secs(10));
+
+    let mut failed = 0;
+    for d in days {
+        let counts = (1..=2).map(|p| d.variants(p).len()).collect::<Vec<_>>();
+        if counts.iter().all(|&n| n < 2) {
+            continue;
+        }
+
+        let cases = check::cases(d, cases);
+        let found = check::check(d, &cases, budget);
+        println!("day {:>2}: {} cases, {} variants of part 1, {} of part 2, {} disagreements",
+            d.day, cases.len(), counts[0], counts[1], found.len());
+        for f in &found {
+            println!("  part {} on {}:", f.part, f.case);
+            for (name, answer) in &f.answers {
+                println!("    {name:<12} {answer}");
+            }
+        }
+        failed += found.len();
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(miette!("{n} cases where variants disagree")),
+    }
+}
+
+fn print_part(config: &Config, r: &run::PartRun) {
+    match config.format {
+        Format::Text => println!("{r}"),
+        Format::Json => println!("{}", r.to_json()),
+    }
+}
+
+fn shows_progress(config: &Config, d: &registry::Day, part: u32, variant: Option<&str>) -> bool {
+    let name = variant.unwrap_or(registry::DEFAULT_VARIANT);
+    config.format == Format::Text && std::io::stderr().is_terminal() && d.long_solver(part, name).is_some()
+}
+
+// Shows how far a long part has got, on the line its result will go
+fn progress(config: &Config, d: &registry::Day, part: u32, variant: Option<&str>) -> Cancel {
+    let day = d.day;
+    match shows_progress(config, d, part, variant) {
+        true => Cancel::default().with_progress(move |f| eprint!("\r\x1b[Kday {day:>2} part {part}: {:.0}%", f * 100.0)),
+        false => Cancel::default(),
+    }
+}
+
+fn print_skipped(config: &Config, d: &registry::Day, part: u32) {
+    if config.format == Format::Text && !config.runs(d, part) {
+        println!("day {:>2} part {part}: skipped, slow (run with --slow)", d.day);
+    }
+}
+
+// Writes the day's visualization, if it has one and viz_dir is set
+fn draw(config: &Config, d: &registry::Day, input: &str) -> Result<()> {
+    let (Some(dir), Some(viz)) = (&config.viz_dir, d.viz) else { return Ok(()) };
+
+    let path = dir.join(format!("day-{:02}.txt", d.day));
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&path, viz(input)))
+        .into_diagnostic()
+        .map_err(|e| e.wrap_err(format!("unable to write {}", path.display())))?;
+    if config.format == Format::Text {
+        println!("drew {}", path.display());
+    }
+    Ok(())
+}
+
+fn find(config: &Config, day: u32) -> Result<&'static registry::Day> {
+    config.find(day).map_err(|e| miette!("{e}"))
+}
+
+fn client(config: &Config) -> Result<HttpClient> {
+    HttpClient::from_env_for(config.account.as_deref()).into_diagnostic()
+}
+
+fn fetch(config: &Config, day: u32, force: bool) -> Result<()> {
+    let d = find(config, day)?;
+    let path = config.input_path(d);
+    if path.exists() && !force {
+        return Err(miette!("{} already exists, use --force to replace it", path.display()));
+    }
+
+    let cache = Cache::new(cache_dir(), client(config)?);
+    let input = cache.fetch(d.year, day).into_diagnostic()?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).into_diagnostic()?;
+    }
+    std::fs::write(&path, input.trim_end())
+        .into_diagnostic()
+        .map_err(|e| e.wrap_err(format!("unable to write {}", path.display())))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn encrypt(config: &Config, day: Option<u32>) -> Result<()> {
+    let key = Key::from_env().ok_or_else(|| miette!("{} is not set", input::KEY_VAR))?;
+    let days = match day {
+        Some(n) => vec![find(config, n)?],
+        None => config.days(),
+    };
+
+    for d in days {
+        let path = config.input_path(d);
+        if !path.exists() {
+            println!("day {:>2}: no plaintext input, skipped", d.day);
+            continue;
+        }
+
+        let enc = input::encrypted_path(&path);
+        std::fs::write(&enc, input::encrypt(&key, &read_input(&path)?))
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("unable to write {}", enc.display())))?;
+        println!("wrote {}", enc.display());
+    }
+    Ok(())
+}
+
+fn submit(config: &Config, day: u32, part: u32, answer: Option<String>) -> Result<()> {
+    let d = find(config, day)?;
+    let http = client(config)?;
+    let answer = match answer {
+        Some(a) => a,
+        None => {
+            let text = read_input(&config.input_path(d))?;
+            let r = run::run_part_within(d, part, None, &text, config.timeout(), Cancel::default())
+                .ok_or_else(|| miette!("day {day} has no part {part}"))?;
+            println!("{r}");
+            r.answer
+        }
+    };
+    if answer.is_empty() {
+        return Err(miette!("day {day} part {part} has no answer"));
+    }
+
+    let mut ledger = Ledger::load(cache_dir().join(http.account()).join("answers.tsv")).into_diagnostic()?;
+    let now = unix_now();
+    ledger.check(d.year, day, part, &answer, now)
+        .map_err(|r| miette!("not submitting {answer}: {r}"))?;
+
+    let response = http.submit(d.year, day, part, &answer).into_diagnostic()?;
+    ledger.record(Guess {
+        year: d.year,
+        day,
+        part,
+        answer: answer.clone(),
+        verdict: response.verdict,
+        at: now,
+        wait: response.wait,
+    }).into_diagnostic()?;
+
+    match response.verdict {
+        Verdict::Correct => {
+            println!("{answer} is correct");
+            Ok(())
+        }
+        v if response.wait > 0 => Err(miette!("{answer}: {v}, wait {}s before the next answer", response.wait)),
+        v => Err(miette!("{answer}: {v}")),
+    }
+}
+
+fn show(config: &Config, day: u32, no_tests: bool, no_pager: bool) -> Result<()> {
+    let d = find(config, day)?;
+    let text = read_input(&d.dir().join("problem.txt"))?;
+
+    let tty = std::io::stdout().is_terminal();
+    let color = tty && std::env::var_os("NO_COLOR").is_none();
+    let width = terminal_size::terminal_size().map_or(80, |(w, _)| w.0 as usize).min(100);
+
+    let mut page = format!("Day {}: {} ({})\n", d.day, d.title, d.year);
+    let ledgers = status::ledgers(&cache_dir());
+    for part in 1..=2 {
+        page.push_str(&format!("  part {part}: {}\n", status::answer(&ledgers, d, part)));
+    }
+    if !no_tests {
+        page.push_str(&format!("  tests:  {}\n", status::run_tests(d)));
+    }
+    page.push('\n');
+    page.push_str(&show::render(&text, width, color));
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    if no_pager || !tty || pager.is_empty() {
+        print!("{page}");
+        return Ok(());
+    }
+
+    let mut args = pager.split_whitespace();
+    let mut child = std::process::Command::new(args.next().unwrap_or("less"))
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .into_diagnostic()
+        .map_err(|e| e.wrap_err(format!("unable to start {pager}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager closing early is not an error
+        let _ = stdin.write_all(page.as_bytes());
+    }
+    child.wait().into_diagnostic()?;
+    Ok(())
+}
+
+/// Downloaded inputs and submitted answers, by account.
+fn cache_dir() -> PathBuf {
+    registry::workspace_dir().join(".input-cache")
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn read_input(path: &Path) -> Result<String> {
+    input::read(path)
+        .into_diagnostic()
+        .map_err(|e| e.wrap_err(format!("unable to read {}", path.display())))
+}
+
+fn inspect(path: &Path) -> Result<()> {
+    print!("{}", inspect::Profile::new(&read_input(path)?));
+    Ok(())
+}
+
+fn bench(config: &Config, day: Option<u32>, samples: usize, no_record: bool, history: Option<PathBuf>) -> Result<()> {
+    let days = match day {
+        Some(n) => vec![find(config, n)?],
+        None => config.days(),
+    };
+
+    let commit = perf::current_commit().unwrap_or_else(|| "unknown".to_string());
+    let timestamp = unix_now();
+
+    if run::COUNTING {
+        println!("counting allocations, so times are not recorded");
+    }
+
+    let mut recorded = vec![];
+    for d in days {
+        let text = read_input(&config.input_path(d))?;
+
+        println!("--- Day {}: {} ---", d.day, d.title);
+        for p in 1..=2 {
+            if !config.runs(d, p) {
+                print_skipped(config, d, p);
+                continue;
+            }
+            let Some(r) = run::bench_part(d, p, &text, samples) else { continue };
+            println!("{r}");
+
+            recorded.push(perf::Sample {
+                commit: commit.clone(),
+                timestamp,
+                year: r.year,
+                day: r.day,
+                part: r.part,
+                nanos: (!run::COUNTING).then_some(r.elapsed.as_nanos() as u64),
+                alloc: r.alloc,
+            });
+        }
+    }
+
+    if !no_record {
+        let path = history.unwrap_or_else(perf::history_path);
+        perf::append(&path, &recorded)
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("unable to write {}", path.display())))?;
+        println!("recorded {} parts for {commit} in {}", recorded.len(), path.display());
+    }
+    Ok(())
+}
+
+fn perf_diff(base: &str, head: Option<&str>, threshold: f64, history: Option<PathBuf>) -> Result<()> {
+    let path = history.unwrap_or_else(perf::history_path);
+    let samples = perf::load(&path)
+        .into_diagnostic()
+        .map_err(|e| e.wrap_err(format!("unable to read {}", path.display())))?;
+
+    let base = perf::resolve(&samples, base).map_err(|e| miette!("{e}"))?;
+    let head = match head {
+        Some(h) => perf::resolve(&samples, h).map_err(|e| miette!("{e}"))?,
+        None => samples.last().map(|s| s.commit.clone()).ok_or_else(|| miette!("no benchmark runs recorded"))?,
+    };
+
+    println!("{base} -> {head} (threshold {threshold}%)");
+    let changes = perf::diff(&samples, &base, &head, threshold);
+    for c in &changes {
+        let allocs = match (c.base_allocs, c.head_allocs) {
+            (Some(a), Some(b)) => format!("  {a} -> {b} allocs"),
+            _ => String::new(),
+        };
+        let flag = match (c.slower, c.more_allocs) {
+            (true, true) => "  SLOWER, MORE ALLOCS",
+            (true, false) => "  SLOWER",
+            (false, true) => "  MORE ALLOCS",
+            (false, false) => "",
+        };
+        println!("{} day {:>2} part {}: {:>10} -> {:>10}{allocs}{flag}",
+            c.year, c.day, c.part,
+            c.base_nanos.map_or("-".to_string(), |n| format!("{:.2?}", Duration::from_nanos(n))),
+            c.head_nanos.map_or("-".to_string(), |n| format!("{:.2?}", Duration::from_nanos(n))));
+    }
+
+    match changes.iter().filter(|c| c.regressed()).count() {
+        0 => Ok(()),
+        n => Err(miette!("{n} parts regressed against {base}")),
+    }
+}
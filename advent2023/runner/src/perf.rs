@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use shared::alloc::AllocStats;
+use crate::registry::FIRST_YEAR;
+
+const HEADER: &str = "# commit\ttimestamp\tyear\tday\tpart\tnanos\tpeak\tallocations\tbytes";
+
+/// One benchmarked part, as kept in the history file. Runs that count
+/// allocations keep no time, since the counting allocator slows them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub commit: String,
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub nanos: Option<u64>,
+    pub alloc: Option<AllocStats>,
+}
+
+impl Sample {
+    fn to_line(&self) -> String {
+        let alloc = match self.alloc {
+            Some(a) => format!("{}\t{}\t{}", a.peak, a.allocations, a.bytes),
+            None => "-\t-\t-".to_string(),
+        };
+        let nanos = self.nanos.map_or("-".to_string(), |n| n.to_string());
+        format!("{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.commit, self.timestamp, self.year, self.day, self.part, nanos, alloc)
+    }
+
+    fn parse(line: &str) -> Option<Sample> {
+        let first = FIRST_YEAR.to_string();
+        let mut f = line.split('\t').collect::<Vec<_>>();
+        // Lines from before the year column are all from the first year
+        if f.len() == 8 {
+            f.insert(2, &first);
+        }
+        if f.len() != 9 {
+            return None;
+        }
+
+        let alloc = match f[6] {
+            "-" => None,
+            _ => Some(AllocStats {
+                peak: f[6].parse().ok()?,
+                allocations: f[7].parse().ok()?,
+                bytes: f[8].parse().ok()?,
+            }),
+        };
+
+        Some(Sample {
+            commit: f[0].to_string(),
+            timestamp: f[1].parse().ok()?,
+            year: f[2].parse().ok()?,
+            day: f[3].parse().ok()?,
+            part: f[4].parse().ok()?,
+            nanos: match f[5] {
+                "-" => None,
+                n => Some(n.parse().ok()?),
+            },
+            alloc,
+        })
+    }
+}
+
+/// Default location of the history file.
+pub fn history_path() -> PathBuf {
+    crate::registry::workspace_dir().join("perf-history.tsv")
+}
+
+pub fn append(path: &Path, samples: &[Sample]) -> io::Result<()> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if new {
+        writeln!(file, "{HEADER}")?;
+    }
+    for s in samples {
+        writeln!(file, "{}", s.to_line())?;
+    }
+    Ok(())
+}
+
+/// Every sample in the history file, oldest first. Lines that do not
+/// parse are skipped.
+pub fn load(path: &Path) -> io::Result<Vec<Sample>> {
+    let text = std::fs::read_to_string(path)?;
+
+    Ok(text.lines()
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(Sample::parse)
+        .collect())
+}
+
+/// The checked out commit, marked `-dirty` when there are local changes.
+pub fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(crate::registry::workspace_dir())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short=12", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+
+    Some(match dirty {
+        true => format!("{commit}-dirty"),
+        false => commit,
+    })
+}
+
+/// The one commit in the history starting with `prefix`.
+pub fn resolve(samples: &[Sample], prefix: &str) -> Result<String, String> {
+    let mut found = samples.iter()
+        .map(|s| s.commit.as_str())
+        .filter(|c| c.starts_with(prefix))
+        .collect::<Vec<_>>();
+    found.sort();
+    found.dedup();
+
+    match found[..] {
+        [c] => Ok(c.to_string()),
+        [] => Err(format!("no benchmark runs recorded for commit {prefix}")),
+        _ => Err(format!("commit {prefix} is ambiguous: {}", found.join(", "))),
+    }
+}
+
+/// A part benchmarked at both commits.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub base_nanos: Option<u64>,
+    pub head_nanos: Option<u64>,
+    pub base_allocs: Option<usize>,
+    pub head_allocs: Option<usize>,
+    pub slower: bool,
+    pub more_allocs: bool,
+}
+
+impl Change {
+    pub fn regressed(&self) -> bool {
+        self.slower || self.more_allocs
+    }
+}
+
+fn median<T: Ord + Copy>(mut v: Vec<T>) -> Option<T> {
+    v.sort();
+    v.get(v.len() / 2).copied()
+}
+
+// A part's median runtime and allocation count
+type Summary = (Option<u64>, Option<usize>);
+
+// Median runtime and allocation count of each part at `commit`, from
+// whichever samples recorded them
+fn summarize(samples: &[Sample], commit: &str) -> BTreeMap<(u32, u32, u32), Summary> {
+    let mut parts: BTreeMap<(u32, u32, u32), Vec<&Sample>> = BTreeMap::new();
+    for s in samples.iter().filter(|s| s.commit == commit) {
+        parts.entry((s.year, s.day, s.part)).or_default().push(s);
+    }
+
+    parts.into_iter()
+        .map(|(k, v)| {
+            let nanos = median(v.iter().filter_map(|s| s.nanos).collect());
+            let allocs = median(v.iter().filter_map(|s| s.alloc.map(|a| a.allocations)).collect());
+            (k, (nanos, allocs))
+        })
+        .collect()
+}
+
+/// Compares every part benchmarked at both `base` and `head`, flagging
+/// those that grew by more than `threshold` percent.
+pub fn diff(samples: &[Sample], base: &str, head: &str, threshold: f64) -> Vec<Change> {
+    let before = summarize(samples, base);
+    let after = summarize(samples, head);
+    let grew = |a: f64, b: f64| b > a * (1.0 + threshold / 100.0);
+
+    after.into_iter()
+        .filter_map(|((year, day, part), (head_nanos, head_allocs))| {
+            let &(base_nanos, base_allocs) = before.get(&(year, day, part))?;
+            let more_allocs = match (base_allocs, head_allocs) {
+                (Some(a), Some(b)) => grew(a as f64, b as f64),
+                _ => false,
+            };
+            let slower = match (base_nanos, head_nanos) {
+                (Some(a), Some(b)) => grew(a as f64, b as f64),
+                _ => false,
+            };
+
+            Some(Change {
+                year,
+                day,
+                part,
+                base_nanos,
+                head_nanos,
+                base_allocs,
+                head_allocs,
+                slower,
+                more_allocs,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(commit: &str, day: u32, nanos: Option<u64>, allocations: Option<usize>) -> Sample {
+        Sample {
+            commit: commit.to_string(),
+            timestamp: 1_700_000_000,
+            year: 2023,
+            day,
+            part: 1,
+            nanos,
+            alloc: allocations.map(|allocations| AllocStats { peak: 10, allocations, bytes: 20 }),
+        }
+    }
+
+    #[test]
+    fn line_roundtrip() {
+        let a = sample("abc123", 5, Some(1234), Some(7));
+        let b = sample("abc123-dirty", 10, Some(99), None);
+        let c = sample("abc123", 5, None, Some(7));
+
+        assert_eq!(Sample::parse(&a.to_line()), Some(a));
+        assert_eq!(Sample::parse(&b.to_line()), Some(b));
+        assert_eq!(Sample::parse(&c.to_line()), Some(c));
+        assert_eq!(Sample::parse(HEADER), None);
+    }
+
+    #[test]
+    fn parse_without_year() {
+        let s = Sample::parse("abc123\t1700000000\t5\t1\t1234\t-\t-\t-").unwrap();
+        assert_eq!((s.year, s.day, s.part, s.nanos), (2023, 5, 1, Some(1234)));
+    }
+
+    #[test]
+    fn append_then_load() {
+        let path = std::env::temp_dir().join(format!("aoc-perf-{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let first = vec![sample("aaa", 1, Some(10), None)];
+        let second = vec![sample("bbb", 1, Some(12), None), sample("bbb", 2, Some(30), Some(4))];
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+
+        let loaded = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, [first, second].concat());
+    }
+
+    #[test]
+    fn resolve_prefix() {
+        let samples = vec![sample("abc111", 1, Some(1), None), sample("abc222", 1, Some(1), None)];
+
+        assert_eq!(resolve(&samples, "abc1"), Ok("abc111".to_string()));
+        assert!(resolve(&samples, "abc").is_err());
+        assert!(resolve(&samples, "fff").is_err());
+    }
+
+    #[test]
+    fn flag_regressions() {
+        let samples = vec![
+            sample("old", 1, Some(100), Some(10)),
+            sample("old", 1, Some(110), Some(10)),
+            sample("old", 1, Some(90), Some(10)),
+            sample("old", 2, Some(100), Some(10)),
+            sample("old", 3, Some(100), None),
+            sample("new", 1, Some(105), Some(10)),
+            sample("new", 2, Some(100), Some(20)),
+            sample("new", 3, Some(200), None),
+            sample("new", 4, Some(100), None),
+        ];
+
+        let changes = diff(&samples, "old", "new", 10.0);
+        let flags = changes.iter()
+            .map(|c| (c.day, c.slower, c.more_allocs))
+            .collect::<Vec<_>>();
+
+        assert_eq!(flags, vec![(1, false, false), (2, false, true), (3, true, false)]);
+        assert!(!diff(&samples, "old", "new", 150.0).iter().any(|c| c.regressed()));
+    }
+
+    #[test]
+    fn times_and_allocs_from_separate_runs() {
+        let samples = vec![
+            sample("old", 1, Some(100), None),
+            sample("old", 1, None, Some(10)),
+            sample("new", 1, Some(300), None),
+            sample("new", 1, None, Some(10)),
+            sample("new", 2, None, Some(5)),
+            sample("old", 2, Some(100), None),
+        ];
+
+        let changes = diff(&samples, "old", "new", 10.0);
+        assert_eq!(changes[0].base_nanos, Some(100));
+        assert_eq!(changes[0].head_allocs, Some(10));
+        assert!(changes[0].slower && !changes[0].more_allocs);
+        assert!(!changes[1].regressed());
+    }
+}
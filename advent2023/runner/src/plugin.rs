@@ -0,0 +1,233 @@
+//! Solver plugins, loaded from a directory of `cdylib`s at start up.
+//! `shared::plugin` describes what a plugin exports. A plugin's parts are
+//! variants of the registered days, and one named `default` takes over
+//! from the day's own solver.
+
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use shared::plugin::{AbiFn, FreeFn, PartsFn, SolveFn, ABI_VERSION};
+use crate::registry;
+
+/// The functions a plugin exports.
+pub struct Api {
+    pub abi: AbiFn,
+    pub parts: PartsFn,
+    pub solve: SolveFn,
+    pub free: FreeFn,
+}
+
+/// A loaded plugin. Plugins stay loaded until the runner exits.
+pub struct Plugin {
+    pub path: PathBuf,
+    api: Api,
+    _lib: Option<libloading::Library>,
+}
+
+impl Plugin {
+    /// A plugin whose functions are already at hand, rather than in a
+    /// library to load.
+    pub fn new(path: impl Into<PathBuf>, api: Api) -> Self {
+        Plugin { path: path.into(), api, _lib: None }
+    }
+}
+
+/// One part a plugin solves.
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub variant: &'static str,
+    c_variant: CString,
+    pub plugin: &'static Plugin,
+}
+
+impl Entry {
+    /// Solves the part through the plugin, panicking if it gives no
+    /// answer, as a built-in solver would.
+    pub fn solve(&self, input: &str) -> String {
+        let api = &self.plugin.api;
+        // Safety: the plugin promised these signatures, and the answer is
+        // copied out before the plugin frees it
+        unsafe {
+            let answer = (api.solve)(self.year, self.day, self.part, self.c_variant.as_ptr(), input.as_ptr(), input.len());
+            if answer.is_null() {
+                panic!("{} gave no answer for day {} part {} ({})", self.plugin.path.display(), self.day, self.part, self.variant);
+            }
+            let text = CStr::from_ptr(answer).to_string_lossy().into_owned();
+            (api.free)(answer);
+            text
+        }
+    }
+}
+
+static ENTRIES: RwLock<Vec<&'static Entry>> = RwLock::new(vec![]);
+
+/// The plugin entries for `part` of a day, in the order they were loaded.
+pub fn entries(year: u32, day: u32, part: u32) -> Vec<&'static Entry> {
+    let entries = ENTRIES.read().unwrap_or_else(|e| e.into_inner());
+    entries.iter().filter(|e| (e.year, e.day, e.part) == (year, day, part)).copied().collect()
+}
+
+/// Loads every plugin in `dir`, by the platform's extension for dynamic
+/// libraries, and makes their parts available. Gives the entries loaded.
+pub fn load_dir(dir: &Path) -> Result<Vec<&'static Entry>, String> {
+    let read = std::fs::read_dir(dir).map_err(|e| format!("unable to read {}: {e}", dir.display()))?;
+    let mut paths = read
+        .filter_map(|e| Some(e.ok()?.path()))
+        .filter(|p| p.extension().is_some_and(|x| x == std::env::consts::DLL_EXTENSION))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut loaded = vec![];
+    for path in paths {
+        loaded.extend(load(&path)?);
+    }
+    Ok(loaded)
+}
+
+/// Loads the plugin at `path`.
+pub fn load(path: &Path) -> Result<Vec<&'static Entry>, String> {
+    let error = |e: libloading::Error| format!("unable to load {}: {e}", path.display());
+
+    // Safety: loading runs the library's initializers, which is the point
+    // of a plugin, and the symbols are trusted to have their documented
+    // signatures
+    unsafe {
+        let lib = libloading::Library::new(path).map_err(error)?;
+        let api = Api {
+            abi: *lib.get::<AbiFn>(b"aoc_plugin_abi\0").map_err(error)?,
+            parts: *lib.get::<PartsFn>(b"aoc_plugin_parts\0").map_err(error)?,
+            solve: *lib.get::<SolveFn>(b"aoc_plugin_solve\0").map_err(error)?,
+            free: *lib.get::<FreeFn>(b"aoc_plugin_free\0").map_err(error)?,
+        };
+        install(Plugin { path: path.to_path_buf(), api, _lib: Some(lib) })
+    }
+}
+
+/// Makes a plugin's parts available, after checking it speaks this ABI and
+/// only solves registered parts not already taken by another plugin.
+///
+/// # Safety
+///
+/// The plugin's functions must behave as `shared::plugin` describes.
+pub unsafe fn install(plugin: Plugin) -> Result<Vec<&'static Entry>, String> {
+    let name = plugin.path.display().to_string();
+    let abi = (plugin.api.abi)();
+    if abi != ABI_VERSION {
+        return Err(format!("{name} was built for plugin ABI {abi}, not {ABI_VERSION}"));
+    }
+
+    let mut len = 0;
+    let parts = (plugin.api.parts)(&mut len);
+    let parts = match parts.is_null() {
+        true => &[][..],
+        false => std::slice::from_raw_parts(parts, len),
+    };
+
+    let mut entries = ENTRIES.write().unwrap_or_else(|e| e.into_inner());
+    let mut found: Vec<(u32, u32, u32, &str)> = vec![];
+    for p in parts {
+        let Ok(variant) = CStr::from_ptr(p.variant).to_str() else {
+            return Err(format!("{name}: a variant name of day {} part {} is not UTF-8", p.day, p.part));
+        };
+        let key = (p.year, p.day, p.part, variant);
+        let what = format!("day {} of {} part {} ({variant})", p.day, p.year, p.part);
+
+        if registry::find(p.year, p.day).is_none() || !(1..=2).contains(&p.part) {
+            return Err(format!("{name}: {what} is not a registered part"));
+        }
+        if let Some(e) = entries.iter().find(|e| (e.year, e.day, e.part, e.variant) == key) {
+            return Err(format!("{name}: {what} is already solved by {}", e.plugin.path.display()));
+        }
+        if found.contains(&key) {
+            return Err(format!("{name}: {what} is listed twice"));
+        }
+        found.push(key);
+    }
+
+    let plugin: &'static Plugin = Box::leak(Box::new(plugin));
+    let found = found.into_iter()
+        .map(|(year, day, part, variant)| &*Box::leak(Box::new(Entry {
+            year,
+            day,
+            part,
+            variant: variant.to_string().leak(),
+            c_variant: CString::new(variant).expect("read up to a NUL"),
+            plugin,
+        })))
+        .collect::<Vec<_>>();
+    entries.extend(found.iter().copied());
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use shared::plugin::PluginPart;
+    use crate::registry::PartSolver;
+    use crate::run;
+
+    // Day 6 only, and answers that agree with the day's own, so that the
+    // other tests are not thrown by these entries
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_part1(input: &str) -> String {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+        day_06::solve_part1(input)
+    }
+
+    shared::aoc_plugin! {
+        (2023, 6, 1, "default") => counted_part1,
+        (2023, 6, 2, "closed-form") => day_06::solve_part2,
+    }
+
+    fn api() -> Api {
+        Api { abi: aoc_plugin_abi, parts: aoc_plugin_parts, solve: aoc_plugin_solve, free: aoc_plugin_free }
+    }
+
+    #[test]
+    fn shadow_and_add() {
+        let loaded = unsafe { install(Plugin::new("test-plugin", api())) }.unwrap();
+        assert_eq!(loaded.iter().map(|e| (e.part, e.variant)).collect::<Vec<_>>(), vec![(1, "default"), (2, "closed-form")]);
+
+        let day = registry::find(2023, 6).unwrap();
+        let names = |p| day.variants(p).into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!((names(1), names(2)), (vec!["default"], vec!["default", "closed-form"]));
+        assert!(matches!(day.variant(1, "default"), Some(PartSolver::Plugin(_))));
+
+        let sample = "Time:      7  15   30\nDistance:  9  40  200";
+        let before = CALLS.load(Ordering::Relaxed);
+        assert_eq!(run::run_part(day, 1, sample).unwrap().answer, "288");
+        assert_eq!(CALLS.load(Ordering::Relaxed), before + 1);
+        let run = run::run_part_within(day, 2, Some("closed-form"), sample, None, Default::default()).unwrap();
+        assert_eq!(run.answer, "71503");
+
+        let again = unsafe { install(Plugin::new("again", api())) }.err().unwrap();
+        assert_eq!(again, "again: day 6 of 2023 part 1 (default) is already solved by test-plugin");
+    }
+
+    extern "C" fn future_abi() -> u32 {
+        ABI_VERSION + 1
+    }
+
+    unsafe extern "C" fn unregistered(len: *mut usize) -> *const PluginPart {
+        static PARTS: [PluginPart; 1] = [PluginPart { year: 2023, day: 25, part: 1, variant: c"default".as_ptr() }];
+        *len = PARTS.len();
+        PARTS.as_ptr()
+    }
+
+    #[test]
+    fn reject_plugins() {
+        let old = Plugin::new("old", Api { abi: future_abi, ..api() });
+        assert_eq!(unsafe { install(old) }.err().unwrap(), format!("old was built for plugin ABI {}, not {ABI_VERSION}", ABI_VERSION + 1));
+
+        let day25 = Plugin::new("day25", Api { parts: unregistered, ..api() });
+        assert_eq!(unsafe { install(day25) }.err().unwrap(), "day25: day 25 of 2023 part 1 (default) is not a registered part");
+
+        assert!(load(Path::new("no-such-plugin.so")).is_err());
+        assert!(load_dir(Path::new("no-such-dir")).is_err());
+        assert!(entries(2023, 25, 1).is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use shared::cancel::{Cancel, Cancelled};
+use crate::plugin::{self, Entry};
+
+/// The year whose day crates sit at the top of the workspace. Later years
+/// live in `<year>/day-XX`, as packages named `day-XX-<year>`.
+pub const FIRST_YEAR: u32 = 2023;
+
+/// Solves one part of a puzzle, from the full puzzle input.
+pub type Solver = fn(&str) -> String;
+
+/// Solves a part that can run long, checking `Cancel` as it goes and
+/// reporting progress through it.
+pub type LongSolver = fn(&str, &Cancel) -> Result<String, Cancelled>;
+
+/// Solves a part from a reader, a line at a time, for inputs too big to
+/// load whole.
+pub type StreamSolver = fn(Box<dyn BufRead>) -> io::Result<String>;
+
+/// Name of the implementation in `Day::parts`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// An implementation of a part, from its day crate or from a plugin.
+#[derive(Clone, Copy)]
+pub enum PartSolver {
+    Builtin(Solver),
+    Plugin(&'static Entry),
+}
+
+impl PartSolver {
+    pub fn solve(&self, input: &str) -> String {
+        match self {
+            PartSolver::Builtin(f) => f(input),
+            PartSolver::Plugin(e) => e.solve(input),
+        }
+    }
+}
+
+/// Another implementation of a part, to be picked by name.
+pub struct Variant {
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: Solver,
+    /// Cancellable version, if it can run long.
+    pub long: Option<LongSolver>,
+    /// Whether it takes minutes.
+    pub slow: bool,
+}
+
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub parts: [Solver; 2],
+    /// Parts whose default implementation takes minutes, and only run
+    /// when asked for.
+    pub slow: &'static [u32],
+    /// Draws a picture of the solution, if the day has one.
+    pub viz: Option<Solver>,
+    /// Cancellable versions of parts that can run long, by part number.
+    pub long: &'static [(u32, LongSolver)],
+    /// Named implementations of parts, which should all agree.
+    pub variants: &'static [Variant],
+    /// Parts that can be solved from a reader, by part number.
+    pub stream: &'static [(u32, StreamSolver)],
+}
+
+impl Day {
+    /// The day's crate directory.
+    pub fn dir(&self) -> PathBuf {
+        let dir = workspace_dir();
+        let dir = match self.year {
+            FIRST_YEAR => dir,
+            year => dir.join(year.to_string()),
+        };
+        dir.join(format!("day-{:02}", self.day))
+    }
+
+    /// The day's package name, for `cargo -p`.
+    pub fn package(&self) -> String {
+        match self.year {
+            FIRST_YEAR => format!("day-{:02}", self.day),
+            year => format!("day-{:02}-{year}", self.day),
+        }
+    }
+
+    /// Path to the puzzle input for this day.
+    pub fn input_path(&self) -> PathBuf {
+        self.dir().join("input1.txt")
+    }
+
+    /// The solver for `part`, numbered from 1.
+    pub fn solver(&self, part: u32) -> Option<Solver> {
+        let i = usize::try_from(part).ok()?.checked_sub(1)?;
+        self.parts.get(i).copied()
+    }
+
+    /// Every implementation of `part` by name, the default first. Plugins
+    /// add theirs after the day's own, or take over one of the same name.
+    pub fn variants(&self, part: u32) -> Vec<(&'static str, PartSolver)> {
+        let Some(default) = self.solver(part) else { return vec![] };
+
+        let named = self.variants.iter()
+            .filter(|v| v.part == part)
+            .map(|v| (v.name, PartSolver::Builtin(v.solve)));
+        let mut all = std::iter::once((DEFAULT_VARIANT, PartSolver::Builtin(default))).chain(named).collect::<Vec<_>>();
+
+        for e in plugin::entries(self.year, self.day, part) {
+            match all.iter_mut().find(|(n, _)| *n == e.variant) {
+                Some((_, solver)) => *solver = PartSolver::Plugin(e),
+                None => all.push((e.variant, PartSolver::Plugin(e))),
+            }
+        }
+        all
+    }
+
+    /// The implementation of `part` called `name`.
+    pub fn variant(&self, part: u32, name: &str) -> Option<PartSolver> {
+        self.variants(part).into_iter().find(|(n, _)| *n == name).map(|(_, f)| f)
+    }
+
+    // The built-in implementation of `part` called `name`, unless a plugin
+    // has taken it over
+    fn builtin(&self, part: u32, name: &str) -> Option<Option<&Variant>> {
+        match self.variant(part, name)? {
+            PartSolver::Builtin(_) if name == DEFAULT_VARIANT => Some(None),
+            PartSolver::Builtin(_) => self.variants.iter().find(|v| (v.part, v.name) == (part, name)).map(Some),
+            PartSolver::Plugin(_) => None,
+        }
+    }
+
+    /// The cancellable solver for the implementation of `part` called
+    /// `name`, if it has one.
+    pub fn long_solver(&self, part: u32, name: &str) -> Option<LongSolver> {
+        match self.builtin(part, name)? {
+            None => self.long.iter().find(|(p, _)| *p == part).map(|(_, f)| *f),
+            Some(v) => v.long,
+        }
+    }
+
+    /// Whether the implementation of `part` called `name` takes minutes.
+    pub fn is_slow(&self, part: u32, name: &str) -> bool {
+        match self.builtin(part, name) {
+            Some(None) => self.slow.contains(&part),
+            Some(Some(v)) => v.slow,
+            None => false,
+        }
+    }
+
+    /// The streaming solver for `part`, if it has one.
+    pub fn stream_solver(&self, part: u32) -> Option<StreamSolver> {
+        self.stream.iter().find(|(p, _)| *p == part).map(|(_, f)| *f)
+    }
+}
+
+pub static DAYS: &[Day] = &[
+    Day { year: 2023, day: 1, title: "Trebuchet?!", parts: [day_01::solve_part1, day_01::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[(1, day_01::solve_part1_from), (2, day_01::solve_part2_from)] },
+    Day { year: 2023, day: 2, title: "Cube Conundrum", parts: [day_02::solve_part1, day_02::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[(1, day_02::solve_part1_from), (2, day_02::solve_part2_from)] },
+    Day { year: 2023, day: 3, title: "Gear Ratios", parts: [day_03::solve_part1, day_03::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[] },
+    Day { year: 2023, day: 4, title: "Scratchcards", parts: [day_04::solve_part1, day_04::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[(1, day_04::solve_part1_from), (2, day_04::solve_part2_from)] },
+    Day {
+        year: 2023, day: 5, title: "If You Give A Seed A Fertilizer", parts: [day_05::solve_part1, day_05::solve_part2_intervals], slow: &[], viz: None, long: &[],
+        variants: &[
+            Variant { part: 2, name: "brute", solve: day_05::solve_part2, long: Some(day_05::solve_part2_with), slow: true },
+        ],
+        stream: &[],
+    },
+    Day { year: 2023, day: 6, title: "Wait For It", parts: [day_06::solve_part1, day_06::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[] },
+    Day { year: 2023, day: 7, title: "Camel Cards", parts: [day_07::solve_part1, day_07::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[(1, day_07::solve_part1_from)] },
+    Day { year: 2023, day: 8, title: "Haunted Wasteland", parts: [day_08::solve_part1, day_08::solve_part2], slow: &[], viz: None, long: &[(1, day_08::solve_part1_with)], variants: &[], stream: &[] },
+    Day { year: 2023, day: 9, title: "Mirage Maintenance", parts: [day_09::solve_part1, day_09::solve_part2], slow: &[], viz: None, long: &[], variants: &[], stream: &[(1, day_09::solve_part1_from), (2, day_09::solve_part2_from)] },
+    Day { year: 2023, day: 10, title: "Pipe Maze", parts: [day_10::solve_part1, day_10::solve_part2], slow: &[], viz: Some(day_10::visualize), long: &[], variants: &[], stream: &[] },
+];
+
+pub fn find(year: u32, day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| (d.year, d.day) == (year, day))
+}
+
+/// The most recent year with any days registered.
+pub fn latest_year() -> u32 {
+    DAYS.iter().map(|d| d.year).max().unwrap_or(FIRST_YEAR)
+}
+
+/// The `advent2023` directory holding the day crates.
+pub fn workspace_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner lives inside the workspace")
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_in_order() {
+        assert!(DAYS.windows(2).all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+        assert!(DAYS.iter().all(|d| d.input_path().exists() || shared::input::encrypted_path(&d.input_path()).exists()));
+    }
+
+    #[test]
+    fn find_day() {
+        assert_eq!(find(2023, 7).map(|d| d.title), Some("Camel Cards"));
+        assert!(find(2023, 26).is_none());
+        assert!(find(2022, 7).is_none());
+
+        let d9 = find(2023, 9).unwrap();
+        assert!(d9.solver(0).is_none());
+        assert!(d9.solver(3).is_none());
+        assert_eq!(d9.solver(1).unwrap()("0 3 6 9 12 15"), "18");
+        assert_eq!(d9.solver(2).unwrap()("0 3 6 9 12 15"), "-3");
+    }
+
+    #[test]
+    fn find_variants() {
+        let d5 = find(2023, 5).unwrap();
+        let names = d5.variants(2).iter().map(|(n, _)| *n).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["default", "brute"]);
+        assert_eq!(find(2023, 9).unwrap().variants(1).len(), 1);
+        assert!(d5.variants(3).is_empty());
+        assert_eq!(d5.variant(2, "brute").unwrap().solve("seeds: 5 2\n\nseed-to-soil map:\n1 5 1"), "1");
+        assert!(d5.variant(1, "brute").is_none());
+
+        assert!(d5.is_slow(2, "brute") && !d5.is_slow(2, DEFAULT_VARIANT));
+        assert!(d5.long_solver(2, "brute").is_some());
+        assert!(d5.long_solver(2, DEFAULT_VARIANT).is_none());
+        assert!(find(2023, 8).unwrap().long_solver(1, DEFAULT_VARIANT).is_some());
+    }
+
+    #[test]
+    fn year_layout() {
+        let first = find(2023, 4).unwrap();
+        let later = Day { year: 2024, day: 4, ..*first };
+
+        assert_eq!(first.dir(), workspace_dir().join("day-04"));
+        assert_eq!(first.package(), "day-04");
+        assert_eq!(later.dir(), workspace_dir().join("2024").join("day-04"));
+        assert_eq!(later.package(), "day-04-2024");
+    }
+
+    // Renders the template as `just create day-04 2024` would, into a
+    // workspace of its own, and builds it
+    #[test]
+    fn template_builds_for_later_years() {
+        let out = workspace_dir().join("target").join("template-test");
+        let day = out.join("2024").join("day-04");
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let globals = liquid::object!({ "project-name": "day-04", "crate_name": "day_04", "year": "2024" });
+
+        for file in ["Cargo.toml", "src/lib.rs", "src/main.rs"] {
+            let text = std::fs::read_to_string(workspace_dir().join("template").join(file)).unwrap();
+            let path = day.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, parser.parse(&text).unwrap().render(&globals).unwrap()).unwrap();
+        }
+
+        // The same shared dependencies as this workspace, and the same
+        // versions of them
+        let mut manifest = std::fs::read_to_string(workspace_dir().join("Cargo.toml")).unwrap()
+            .parse::<toml::Table>().unwrap();
+        let workspace = manifest["workspace"].as_table_mut().unwrap();
+        workspace.remove("default-members");
+        workspace["members"] = vec!["2024/day-04"].into();
+        workspace["dependencies"]["shared"]["path"] = workspace_dir().join("shared").to_string_lossy().as_ref().into();
+        std::fs::write(out.join("Cargo.toml"), manifest.to_string()).unwrap();
+        let _ = std::fs::copy(workspace_dir().join("Cargo.lock"), out.join("Cargo.lock"));
+
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["build", "-q"])
+            .current_dir(&out)
+            .env("CARGO_TARGET_DIR", out.join("target"))
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let exe = format!("day-04-2024{}", std::env::consts::EXE_SUFFIX);
+        assert!(out.join("target").join("debug").join(exe).exists());
+    }
+}
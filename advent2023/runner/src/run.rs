@@ -0,0 +1,456 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::json;
+use shared::alloc::AllocStats;
+use shared::cancel::{self, Cancel, Cancelled, Outcome};
+use crate::config::Config;
+use crate::registry::{Day, LongSolver, PartSolver, DEFAULT_VARIANT};
+
+/// Whether the counting allocator is installed in this build.
+pub const COUNTING: bool = cfg!(feature = "count-alloc");
+
+/// How solving a part ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Solved,
+    /// Given up on when the time budget ran out.
+    TimedOut,
+    Panicked(String),
+}
+
+/// The outcome of solving one part.
+#[derive(Debug)]
+pub struct PartRun {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The implementation used, when not the default.
+    pub variant: Option<&'static str>,
+    /// Empty unless the part was solved.
+    pub answer: String,
+    pub elapsed: Duration,
+    /// CPU time the solving thread used, which leaves out time it spent
+    /// waiting for a core. Zero unless the part was solved.
+    pub cpu: Duration,
+    pub alloc: Option<AllocStats>,
+    pub status: Status,
+}
+
+pub fn run_part(day: &Day, part: u32, input: &str) -> Option<PartRun> {
+    solve(day, part, None, input, COUNTING, &Cancel::default())
+}
+
+/// Like `run_part`, but gives up after `timeout`, cancelling parts that
+/// can be cancelled. Anything else that runs over is left behind on its
+/// thread, since there is no way to stop it. `cancel` also carries
+/// progress reports from parts that make them. `variant` picks an
+/// implementation other than the default.
+pub fn run_part_within(
+    day: &'static Day,
+    part: u32,
+    variant: Option<&'static str>,
+    input: &str,
+    timeout: Option<Duration>,
+    cancel: Cancel,
+) -> Option<PartRun> {
+    solve_within(day, part, variant, input, COUNTING, timeout, cancel)
+}
+
+fn solve_within(
+    day: &'static Day,
+    part: u32,
+    variant: Option<&'static str>,
+    input: &str,
+    count: bool,
+    timeout: Option<Duration>,
+    cancel: Cancel,
+) -> Option<PartRun> {
+    let Some(timeout) = timeout else { return solve(day, part, variant, input, count, &cancel) };
+    pick(day, part, variant)?;
+
+    let input = input.to_string();
+    let stopped = |elapsed, status| PartRun {
+        year: day.year,
+        day: day.day,
+        part,
+        variant,
+        answer: String::new(),
+        elapsed,
+        cpu: Duration::ZERO,
+        alloc: None,
+        status,
+    };
+
+    match cancel::within(timeout, &cancel, move |c| solve(day, part, variant, &input, count, c)) {
+        Outcome::Done(r) => r,
+        Outcome::TimedOut => Some(stopped(timeout, Status::TimedOut)),
+        Outcome::Panicked(msg) => Some(stopped(Duration::ZERO, Status::Panicked(msg))),
+    }
+}
+
+// The solver for `part`, and its cancellable version if it has one
+fn pick(day: &Day, part: u32, variant: Option<&str>) -> Option<(PartSolver, Option<LongSolver>)> {
+    let name = variant.unwrap_or(DEFAULT_VARIANT);
+    Some((day.variant(part, name)?, day.long_solver(part, name)))
+}
+
+fn solve(day: &Day, part: u32, variant: Option<&'static str>, input: &str, count: bool, cancel: &Cancel) -> Option<PartRun> {
+    let (solver, long) = pick(day, part, variant)?;
+
+    let timed = || {
+        let (start, cpu) = (Instant::now(), thread_cpu());
+        let answer = match long {
+            Some(f) => f(input, cancel),
+            None => Ok(solver.solve(input)),
+        };
+        (answer, start.elapsed(), thread_cpu().saturating_sub(cpu))
+    };
+
+    let ((answer, elapsed, cpu), alloc) = match count {
+        true => {
+            let (r, stats) = shared::alloc::measure(timed);
+            (r, Some(stats))
+        }
+        false => (timed(), None),
+    };
+
+    let (answer, status) = match answer {
+        Ok(a) => (a, Status::Solved),
+        Err(Cancelled) => (String::new(), Status::TimedOut),
+    };
+    Some(PartRun { year: day.year, day: day.day, part, variant, answer, elapsed, cpu, alloc, status })
+}
+
+/// Solves a part from the file at `path` a line at a time, if it can be,
+/// so the input is never loaded whole. There is no time budget.
+pub fn stream_part(day: &Day, part: u32, path: &Path) -> Option<io::Result<PartRun>> {
+    let solver = day.stream_solver(part)?;
+
+    let timed = || {
+        let (start, cpu) = (Instant::now(), thread_cpu());
+        let answer = File::open(path).and_then(|f| solver(Box::new(BufReader::new(f))));
+        (answer, start.elapsed(), thread_cpu().saturating_sub(cpu))
+    };
+
+    let ((answer, elapsed, cpu), alloc) = match COUNTING {
+        true => {
+            let (r, stats) = shared::alloc::measure(timed);
+            (r, Some(stats))
+        }
+        false => (timed(), None),
+    };
+
+    Some(answer.map(|answer| PartRun {
+        year: day.year,
+        day: day.day,
+        part,
+        variant: None,
+        answer,
+        elapsed,
+        cpu,
+        alloc,
+        status: Status::Solved,
+    }))
+}
+
+/// Solves a part `samples` times and keeps the run with the median time.
+pub fn bench_part(day: &Day, part: u32, input: &str, samples: usize) -> Option<PartRun> {
+    let mut runs = (0..samples.max(1))
+        .map(|_| run_part(day, part, input))
+        .collect::<Option<Vec<_>>>()?;
+
+    runs.sort_by_key(|r| r.elapsed);
+    Some(runs.swap_remove(runs.len() / 2))
+}
+
+/// Both parts of one day, solved on the same worker thread.
+pub struct DayRun {
+    pub day: &'static Day,
+    pub parts: Vec<PartRun>,
+    /// CPU time spent solving this day's parts.
+    pub cpu: Duration,
+}
+
+/// Solves every part of `days` that `config` allows on `jobs` threads,
+/// returning the results in the same order as `days`. The allocation
+/// counters are global, so heap use is only reported when running on a
+/// single thread.
+pub fn run_days<E: Send>(
+    days: &[&'static Day],
+    jobs: usize,
+    config: &Config,
+    input: impl Fn(&Day) -> Result<String, E> + Sync,
+) -> Vec<Result<DayRun, E>> {
+    let next = AtomicUsize::new(0);
+    let count = COUNTING && jobs <= 1;
+
+    let mut results = thread::scope(|s| {
+        let workers = (0..jobs.clamp(1, days.len().max(1)))
+            .map(|_| s.spawn(|| {
+                let mut done = vec![];
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&day) = days.get(i) else { break };
+
+                    let run = input(day).map(|text| {
+                        let parts = (1..=2)
+                            .filter(|&p| config.runs(day, p))
+                            .filter_map(|p| solve_within(day, p, None, &text, count, config.timeout(), Cancel::default()))
+                            .collect::<Vec<_>>();
+                        let cpu = parts.iter().map(|r| r.cpu).sum();
+                        DayRun { day, parts, cpu }
+                    });
+                    done.push((i, run));
+                }
+                done
+            }))
+            .collect::<Vec<_>>();
+
+        workers.into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+// CPU time the calling thread has used so far
+#[cfg(unix)]
+fn thread_cpu() -> Duration {
+    let mut t = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // Safety: `t` is ours for the call to fill in
+    unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut t) };
+    Duration::new(t.tv_sec as u64, t.tv_nsec as u32)
+}
+
+#[cfg(windows)]
+fn thread_cpu() -> Duration {
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::System::Threading::{GetCurrentThread, GetThreadTimes};
+
+    let zero = || FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 };
+    let (mut created, mut exited, mut kernel, mut user) = (zero(), zero(), zero(), zero());
+    // Safety: the current thread's pseudo handle is always valid, and the
+    // times are ours for the call to fill in
+    unsafe { GetThreadTimes(GetCurrentThread(), &mut created, &mut exited, &mut kernel, &mut user) };
+
+    // In units of 100ns
+    let ticks = |t: FILETIME| ((t.dwHighDateTime as u64) << 32) | t.dwLowDateTime as u64;
+    Duration::from_nanos((ticks(kernel) + ticks(user)) * 100)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn thread_cpu() -> Duration {
+    Duration::ZERO
+}
+
+impl PartRun {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "variant": self.variant.unwrap_or(DEFAULT_VARIANT),
+            "answer": self.answer,
+            "nanos": self.elapsed.as_nanos() as u64,
+            "status": match &self.status {
+                Status::Solved => "solved",
+                Status::TimedOut => "timed_out",
+                Status::Panicked(_) => "panicked",
+            },
+            "alloc": self.alloc.map(|a| json!({
+                "peak": a.peak,
+                "allocations": a.allocations,
+                "bytes": a.bytes,
+            })),
+        })
+    }
+}
+
+impl fmt::Display for PartRun {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answer = match self.status {
+            Status::Solved => &self.answer,
+            Status::TimedOut => "timed out",
+            Status::Panicked(_) => "panicked",
+        };
+        let part = match self.variant {
+            Some(v) => format!("{} ({v})", self.part),
+            None => self.part.to_string(),
+        };
+        write!(f, "day {:>2} part {part}: {:<16} {:>10}",
+            self.day, answer, format!("{:.2?}", self.elapsed))?;
+
+        if let Some(a) = self.alloc {
+            write!(f, "  peak {}, {} allocs, {} total",
+                fmt_bytes(a.peak), a.allocations, fmt_bytes(a.bytes))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn fmt_bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut v = n as f64;
+    let mut unit = 0;
+    while v >= 1024.0 && unit < UNITS.len() - 1 {
+        v /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{n} B"),
+        _ => format!("{v:.1} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(fmt_bytes(0), "0 B");
+        assert_eq!(fmt_bytes(1023), "1023 B");
+        assert_eq!(fmt_bytes(1536), "1.5 KiB");
+        assert_eq!(fmt_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn run_sample() {
+        let day = crate::registry::find(2023, 9).unwrap();
+        let run = run_part(day, 1, "0 3 6 9 12 15\n1 3 6 10 15 21").unwrap();
+
+        assert_eq!(run.answer, "46");
+        assert_eq!(run.alloc.is_some(), COUNTING);
+        assert!(run_part(day, 3, "").is_none());
+    }
+
+    #[test]
+    fn stream_samples() {
+        for day in crate::registry::DAYS.iter().filter(|d| !d.stream.is_empty()) {
+            let samples = (2..).map(|n| day.dir().join(format!("input{n}.txt"))).take_while(|p| p.exists());
+            for path in samples {
+                let text = std::fs::read_to_string(&path).unwrap();
+                for &(part, _) in day.stream {
+                    let streamed = stream_part(day, part, &path).unwrap().unwrap();
+                    assert_eq!(streamed.answer, run_part(day, part, &text).unwrap().answer, "day {} part {part} on {path:?}", day.day);
+                }
+            }
+        }
+
+        let day = crate::registry::find(2023, 9).unwrap();
+        assert!(stream_part(day, 1, Path::new("no-such-input.txt")).unwrap().is_err());
+        assert!(stream_part(crate::registry::find(2023, 3).unwrap(), 1, Path::new("")).is_none());
+    }
+
+    #[test]
+    fn run_days_in_order() {
+        let days = crate::registry::DAYS.iter()
+            .filter(|d| [1, 6, 9].contains(&d.day))
+            .collect::<Vec<_>>();
+        let input = |d: &Day| match d.day {
+            1 => Ok("a1b2c".to_string()),
+            9 => Ok("0 3 6 9 12 15".to_string()),
+            _ => Err(d.day),
+        };
+
+        let runs = run_days(&days, 3, &Config::default(), input);
+        let order = runs.iter()
+            .map(|r| r.as_ref().map(|d| d.day.day).unwrap_or_else(|e| *e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(order, vec![1, 6, 9]);
+        assert!(runs[1].is_err());
+        assert_eq!(runs[0].as_ref().unwrap().parts[0].answer, "12");
+
+        let d9 = runs[2].as_ref().unwrap();
+        assert_eq!(d9.parts.iter().map(|r| r.answer.as_str()).collect::<Vec<_>>(), vec!["18", "-3"]);
+        assert_eq!(d9.cpu, d9.parts[0].cpu + d9.parts[1].cpu);
+        assert!(d9.parts.iter().all(|r| r.alloc.is_none()));
+    }
+
+    #[test]
+    fn cpu_leaves_out_waiting() {
+        let before = thread_cpu();
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(thread_cpu() - before < Duration::from_millis(50));
+
+        // Other tests share the cores, so busy work only needs to show up
+        // eventually
+        let (start, before) = (Instant::now(), thread_cpu());
+        while thread_cpu() - before < Duration::from_millis(20) {
+            std::hint::black_box(0);
+            assert!(start.elapsed() < Duration::from_secs(10), "busy work used no cpu");
+        }
+    }
+
+    #[test]
+    fn time_out() {
+        let budget = Some(Duration::from_secs(5));
+        let day = crate::registry::find(2023, 9).unwrap();
+        let run = run_part_within(day, 1, None, "0 3 6 9 12 15", budget, Cancel::default()).unwrap();
+        assert_eq!((run.answer.as_str(), &run.status), ("18", &Status::Solved));
+
+        // Long enough to outlast a zero timeout
+        let big = (0..2000).map(|i| (0..20).map(|j| (i * j).to_string()).collect::<Vec<_>>().join(" ")).collect::<Vec<_>>().join("\n");
+        let run = run_part_within(day, 1, None, &big, Some(Duration::ZERO), Cancel::default()).unwrap();
+        assert_eq!(run.status, Status::TimedOut);
+        assert_eq!(run.to_string(), "day  9 part 1: timed out            0.00ns");
+        assert_eq!(run.to_json()["status"], "timed_out");
+
+        let run = run_part_within(day, 1, None, "0 3 x", budget, Cancel::default()).unwrap();
+        assert!(matches!(run.status, Status::Panicked(_)));
+    }
+
+    #[test]
+    fn pick_variant() {
+        let day = crate::registry::find(2023, 5).unwrap();
+        let sample = "seeds: 5 2\n\nseed-to-soil map:\n1 5 1";
+
+        let run = run_part_within(day, 2, Some("brute"), sample, None, Cancel::default()).unwrap();
+        assert_eq!((run.answer.as_str(), run.variant), ("1", Some("brute")));
+        assert!(run.to_string().starts_with("day  5 part 2 (brute): 1 "));
+        assert_eq!(run.to_json()["variant"], "brute");
+
+        assert!(run_part_within(day, 2, Some("nope"), sample, None, Cancel::default()).is_none());
+        assert!(run_part_within(day, 1, Some("brute"), sample, None, Cancel::default()).is_none());
+    }
+
+    #[test]
+    fn cancel_long_part() {
+        let day = crate::registry::find(2023, 8).unwrap();
+        // AAA is on a ring of 3000 nodes, away from ZZZ, so the walk only
+        // ends after going round every node at every instruction
+        let ring = (0..3000).map(|i| format!("N{i} = (N{0}, N{0})\n", (i + 1) % 3000)).collect::<String>();
+        let long = format!("{}\n\nAAA = (N0, N0)\n{ring}ZZZ = (ZZZ, ZZZ)", "L".repeat(100_000));
+        let cancel = Cancel::default();
+
+        let run = run_part_within(day, 1, None, &long, Some(Duration::from_millis(50)), cancel.clone()).unwrap();
+        assert_eq!(run.status, Status::TimedOut);
+        assert!(cancel.is_cancelled());
+
+        // Named variants are cancelled too
+        let day = crate::registry::find(2023, 5).unwrap();
+        let cancel = Cancel::default();
+        let run = run_part_within(day, 2, Some("brute"), "seeds: 0 100000000000\n\nseed-to-soil map:\n1 5 1", Some(Duration::from_millis(50)), cancel.clone()).unwrap();
+        assert_eq!(run.status, Status::TimedOut);
+        assert!(cancel.is_cancelled());
+    }
+
+    #[test]
+    fn bench_sample() {
+        let day = crate::registry::find(2023, 9).unwrap();
+        let run = bench_part(day, 2, "0 3 6 9 12 15", 3).unwrap();
+
+        assert_eq!(run.answer, "-3");
+        assert!(bench_part(day, 3, "", 3).is_none());
+    }
+}
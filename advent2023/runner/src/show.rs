@@ -0,0 +1,172 @@
+//! Rendering a day's `problem.txt` for the terminal.
+
+const BOLD: &str = "\x1b[1m";
+const YELLOW: &str = "\x1b[1;33m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, PartialEq)]
+enum Block<'a> {
+    Heading(&'a str),
+    Prose(&'a str),
+    /// Example input, or anything else laid out line by line.
+    Code(Vec<&'a str>),
+}
+
+// Paragraphs are blank line separated. Prose is always one long line ending
+// in punctuation; anything else keeps its line breaks.
+fn blocks(text: &str) -> Vec<Block<'_>> {
+    let mut result = vec![];
+    let mut lines = text.lines().peekable();
+
+    while lines.peek().is_some() {
+        let para = lines.by_ref()
+            .skip_while(|l| l.trim().is_empty())
+            .take_while(|l| !l.trim().is_empty())
+            .collect::<Vec<_>>();
+
+        let block = match para[..] {
+            [] => continue,
+            [l] if l.starts_with("---") && l.ends_with("---") => Block::Heading(l.trim_matches(|c| c == '-' || c == ' ')),
+            [l] if !l.starts_with(' ') && l.ends_with(['.', '?', '!', ':', '"', ')']) => Block::Prose(l),
+            _ => Block::Code(para),
+        };
+        result.push(block);
+    }
+    result
+}
+
+/// Greedy word wrap, never splitting a word.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut rows = vec![];
+    let mut row = String::new();
+
+    for word in line.split_whitespace() {
+        if !row.is_empty() && row.len() + 1 + word.len() > width {
+            rows.push(std::mem::take(&mut row));
+        }
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(word);
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+// Numbers standing on their own, not the digits inside a word like "day-05"
+// or "a1b2".
+fn emphasize_numbers(line: &str) -> String {
+    let mut result = String::new();
+    let mut chars = line.char_indices().peekable();
+    let mut prev = ' ';
+
+    while let Some((i, c)) = chars.next() {
+        if c.is_ascii_digit() && !prev.is_alphanumeric() && prev != '-' {
+            let mut end = i + 1;
+            while let Some(&(j, d)) = chars.peek() {
+                if !d.is_ascii_digit() && d != ',' {
+                    break;
+                }
+                end = j + 1;
+                chars.next();
+            }
+            let number = line[i..end].trim_end_matches(',');
+            let after = chars.peek().map_or(' ', |&(_, d)| d);
+
+            if !after.is_alphanumeric() {
+                result.push_str(&format!("{BOLD}{number}{RESET}"));
+            } else {
+                result.push_str(number);
+            }
+            result.push_str(&line[i + number.len()..end]);
+            prev = line[..end].chars().last().unwrap_or(c);
+            continue;
+        }
+        result.push(c);
+        prev = c;
+    }
+    result
+}
+
+/// The problem text wrapped to `width` columns, with ANSI colours when
+/// `color` is set.
+pub fn render(text: &str, width: usize, color: bool) -> String {
+    let width = width.max(20);
+    let mut out = String::new();
+
+    for block in blocks(text) {
+        match block {
+            Block::Heading(h) => match color {
+                true => out.push_str(&format!("{YELLOW}--- {h} ---{RESET}\n")),
+                false => out.push_str(&format!("--- {h} ---\n")),
+            },
+            Block::Prose(p) => {
+                for row in wrap(p, width) {
+                    match color {
+                        true => out.push_str(&emphasize_numbers(&row)),
+                        false => out.push_str(&row),
+                    }
+                    out.push('\n');
+                }
+            }
+            Block::Code(lines) => {
+                for l in lines {
+                    match color {
+                        true => out.push_str(&format!("    {GREEN}{l}{RESET}\n")),
+                        false => out.push_str(&format!("    {l}\n")),
+                    }
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_blocks() {
+        let text = "--- Day 1: Trebuchet?! ---\n\nFor example:\n\n1abc2\npqr3stu8vwx\n\nseeds: 79 14\n\n    Seed 79, soil 81.\n\n\n--- Part Two ---\n";
+
+        assert_eq!(blocks(text), vec![
+            Block::Heading("Day 1: Trebuchet?!"),
+            Block::Prose("For example:"),
+            Block::Code(vec!["1abc2", "pqr3stu8vwx"]),
+            Block::Code(vec!["seeds: 79 14"]),
+            Block::Code(vec!["    Seed 79, soil 81."]),
+            Block::Heading("Part Two"),
+        ]);
+    }
+
+    #[test]
+    fn wrap_words() {
+        assert_eq!(wrap("aa bb cc dd", 5), vec!["aa bb", "cc dd"]);
+        assert_eq!(wrap("aa bb cc", 4), vec!["aa", "bb", "cc"]);
+        assert_eq!(wrap("abcdefgh ij", 4), vec!["abcdefgh", "ij"]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn numbers() {
+        let bold = |n: &str| format!("{BOLD}{n}{RESET}");
+
+        assert_eq!(emphasize_numbers("produces 142."), format!("produces {}.", bold("142")));
+        assert_eq!(emphasize_numbers("are 12, 38, and 77"), format!("are {}, {}, and {}", bold("12"), bold("38"), bold("77")));
+        assert_eq!(emphasize_numbers("a total of 1,000,000 times"), format!("a total of {} times", bold("1,000,000")));
+        assert_eq!(emphasize_numbers("treb7uchet and day-05 and 2nd"), "treb7uchet and day-05 and 2nd");
+    }
+
+    #[test]
+    fn render_plain() {
+        let text = "--- Day 9 ---\n\nThe answer is 18 here.\n\n0 3 6";
+
+        assert_eq!(render(text, 20, false), "--- Day 9 ---\n\nThe answer is 18\nhere.\n\n    0 3 6\n\n");
+        assert!(render(text, 80, true).contains(&format!("{BOLD}18{RESET}")));
+    }
+}
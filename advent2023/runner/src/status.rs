@@ -0,0 +1,131 @@
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+use shared::submit::{Ledger, Verdict};
+use crate::registry::{self, Day};
+
+/// Where a part stands with adventofcode.com.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Accepted(String),
+    /// Not solved yet, after this many wrong guesses.
+    Unsolved(usize),
+}
+
+impl Answer {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Answer::Accepted(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Accepted(a) => write!(f, "{a} (accepted)"),
+            Answer::Unsolved(0) => write!(f, "not submitted"),
+            Answer::Unsolved(n) => write!(f, "not solved, {n} wrong guesses"),
+        }
+    }
+}
+
+/// The answer ledgers of every account under `cache`.
+pub fn ledgers(cache: &Path) -> Vec<Ledger> {
+    std::fs::read_dir(cache)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| Ledger::load(e.path().join("answers.tsv")).ok())
+        .collect()
+}
+
+/// The answer accepted in any of `ledgers`, or how many guesses missed.
+pub fn answer(ledgers: &[Ledger], d: &Day, part: u32) -> Answer {
+    let guesses = ledgers.iter()
+        .flat_map(|l| l.guesses(d.year, d.day, part))
+        .collect::<Vec<_>>();
+
+    match guesses.iter().find(|g| g.verdict == Verdict::Correct) {
+        Some(g) => Answer::Accepted(g.answer.clone()),
+        None => Answer::Unsolved(guesses.iter().filter(|g| g.verdict.is_wrong()).count()),
+    }
+}
+
+/// How a day's `cargo test` went.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tests {
+    Ran { passed: usize, failed: usize },
+    /// The crate did not build, or cargo could not be started.
+    Broken(String),
+}
+
+impl Tests {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Tests::Ran { failed: 0, .. })
+    }
+}
+
+impl fmt::Display for Tests {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tests::Ran { passed, failed } => {
+                let status = if self.is_ok() { "ok" } else { "FAILED" };
+                write!(f, "{status}, {passed} passed, {failed} failed")
+            }
+            Tests::Broken(why) => write!(f, "{why}"),
+        }
+    }
+}
+
+/// Runs the tests in the day's crate.
+pub fn run_tests(d: &Day) -> Tests {
+    let output = Command::new(env!("CARGO"))
+        .args(["test", "-q", "-p", &d.package()])
+        .current_dir(registry::workspace_dir())
+        .output();
+
+    match output {
+        Ok(output) => parse_tests(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => Tests::Broken("unable to run cargo test".to_string()),
+    }
+}
+
+// Adds up the `test result:` lines, one per test target
+fn parse_tests(stdout: &str) -> Tests {
+    let counts = stdout.lines()
+        .filter_map(|l| l.strip_prefix("test result: "))
+        .filter_map(|l| l.split_once(". "))
+        .map(|(_, counts)| {
+            let count = |what: &str| counts.split("; ")
+                .find_map(|c| c.strip_suffix(what)?.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            (count("passed"), count("failed"))
+        })
+        .collect::<Vec<_>>();
+
+    match counts.is_empty() {
+        true => Tests::Broken("build failed".to_string()),
+        false => Tests::Ran {
+            passed: counts.iter().map(|c| c.0).sum(),
+            failed: counts.iter().map(|c| c.1).sum(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test_results() {
+        let stdout = "\nrunning 5 tests\n.....\n\
+            test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\n\n\
+            running 1 test\nF\n\
+            test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+
+        let tests = parse_tests(stdout);
+        assert_eq!(tests, Tests::Ran { passed: 5, failed: 1 });
+        assert_eq!(tests.to_string(), "FAILED, 5 passed, 1 failed");
+        assert!(parse_tests("test result: ok. 2 passed; 0 failed; 1 ignored").is_ok());
+        assert_eq!(parse_tests("error[E0308]: mismatched types"), Tests::Broken("build failed".to_string()));
+    }
+}
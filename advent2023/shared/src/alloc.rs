@@ -0,0 +1,126 @@
+//! Heap counters for profiling solvers.
+//!
+//! Install [`CountingAlloc`] as the `#[global_allocator]` of a binary,
+//! then wrap the code of interest in [`measure`].
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static BASE: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation it makes.
+pub struct CountingAlloc;
+
+/// Heap usage since the last [`reset`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Highest heap in use, above what was in use at the reset.
+    pub peak: usize,
+    /// Number of allocations, counting each reallocation as one.
+    pub allocations: usize,
+    /// Total bytes requested by those allocations.
+    pub bytes: usize,
+}
+
+// A new block of `size` bytes, replacing `freed` bytes on reallocation
+fn record(size: usize, freed: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+
+    if size >= freed {
+        let now = CURRENT.fetch_add(size - freed, Ordering::Relaxed) + size - freed;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    } else {
+        CURRENT.fetch_sub(freed - size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            record(layout.size(), 0);
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            record(layout.size(), 0);
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            record(new_size, layout.size());
+        }
+        p
+    }
+}
+
+/// Zeroes the counters and starts measuring peak heap from the
+/// current usage.
+pub fn reset() {
+    let now = CURRENT.load(Ordering::Relaxed);
+    BASE.store(now, Ordering::Relaxed);
+    PEAK.store(now, Ordering::Relaxed);
+    COUNT.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+}
+
+/// Counters since the last [`reset`].
+pub fn stats() -> AllocStats {
+    AllocStats {
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(BASE.load(Ordering::Relaxed)),
+        allocations: COUNT.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Runs `f` and returns its result along with the heap it used. Only
+/// meaningful when [`CountingAlloc`] is the global allocator, and when
+/// nothing else allocates at the same time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    reset();
+    let result = f();
+    (result, stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        let a = CountingAlloc;
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let large = Layout::from_size_align(1000, 8).unwrap();
+
+        reset();
+        unsafe {
+            let p = a.alloc(small);
+            let q = a.alloc(large);
+            a.dealloc(q, large);
+            let p = a.realloc(p, small, 128);
+            a.dealloc(p, Layout::from_size_align(128, 8).unwrap());
+        }
+
+        assert_eq!(stats(), AllocStats {
+            peak: 1064,
+            allocations: 3,
+            bytes: 1192,
+        });
+    }
+}
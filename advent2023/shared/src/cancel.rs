@@ -0,0 +1,175 @@
+//! Cooperative cancellation for solvers that can run for a long time, and
+//! a way to run one under a time budget.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Handed to a long solver, which checks it as it goes and reports how
+/// far it has got. Clones share the same flag.
+#[derive(Clone, Default)]
+pub struct Cancel {
+    flag: Arc<AtomicBool>,
+    progress: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+}
+
+/// Returned by a solver that stopped because it was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+impl Cancel {
+    /// Calls `f` with each progress report.
+    pub fn with_progress(mut self, f: impl Fn(f64) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// For a solver to `?` on, to stop once cancelled.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.is_cancelled() {
+            true => Err(Cancelled),
+            false => Ok(()),
+        }
+    }
+
+    /// Whether another clone of this token is still alive. The one
+    /// `within` hands its solver lives until the solver returns, so this
+    /// tells whether a solver that was given up on is still running.
+    pub fn in_use(&self) -> bool {
+        Arc::strong_count(&self.flag) > 1
+    }
+
+    /// Reports the fraction of the work done, from 0 to 1.
+    pub fn progress(&self, done: f64) {
+        if let Some(f) = &self.progress {
+            f(done.clamp(0.0, 1.0));
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome<T> {
+    Done(T),
+    /// Still running when the time ran out.
+    TimedOut,
+    Panicked(String),
+}
+
+/// Runs `f` on its own thread, giving up on it after `timeout`. `cancel`
+/// is cancelled then, so a solver that checks it stops soon after; one
+/// that does not is left behind on its thread.
+pub fn within<T: Send + 'static>(
+    timeout: Duration,
+    cancel: &Cancel,
+    f: impl FnOnce(&Cancel) -> T + Send + 'static,
+) -> Outcome<T> {
+    let (tx, rx) = mpsc::channel();
+    let worker = cancel.clone();
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&worker)));
+        let _ = tx.send(result.map_err(|e| message(&*e)));
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(r)) => Outcome::Done(r),
+        Ok(Err(msg)) => Outcome::Panicked(msg),
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Outcome::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panicked("solver thread died".to_string()),
+    }
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "non-string panic".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // Waits until cancelled
+    fn spin(cancel: &Cancel) -> Result<(), Cancelled> {
+        loop {
+            cancel.check()?;
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn cancel_shared_by_clones() {
+        let cancel = Cancel::default();
+        let clone = cancel.clone();
+        assert_eq!(clone.check(), Ok(()));
+
+        cancel.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(spin(&clone), Err(Cancelled));
+    }
+
+    #[test]
+    fn in_use_until_solver_returns() {
+        let cancel = Cancel::default();
+        assert!(!cancel.in_use());
+
+        let (tx, rx) = mpsc::channel::<()>();
+        let outcome = within(Duration::from_millis(10), &cancel, move |_| rx.recv());
+        assert_eq!(outcome, Outcome::TimedOut);
+        assert!(cancel.in_use());
+
+        drop(tx);
+        while cancel.in_use() {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn progress_reported() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let sink = seen.clone();
+        let cancel = Cancel::default().with_progress(move |f| sink.lock().unwrap().push(f));
+
+        cancel.progress(0.25);
+        cancel.progress(1.5);
+        Cancel::default().progress(0.5);
+        assert_eq!(*seen.lock().unwrap(), vec![0.25, 1.0]);
+    }
+
+    #[test]
+    fn run_within() {
+        let budget = Duration::from_secs(5);
+        assert_eq!(within(budget, &Cancel::default(), |_| 42), Outcome::Done(42));
+        assert_eq!(within(budget, &Cancel::default(), |_| -> u32 { panic!("boom") }), Outcome::Panicked("boom".to_string()));
+
+        let cancel = Cancel::default();
+        assert_eq!(within(Duration::from_millis(20), &cancel, spin), Outcome::TimedOut);
+        assert!(cancel.is_cancelled());
+    }
+}
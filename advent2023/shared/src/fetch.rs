@@ -0,0 +1,287 @@
+//! Downloading puzzle inputs, with an on-disk cache so each input is only
+//! requested once per account.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// No session token in the named environment variable.
+    MissingToken(String),
+    /// An account name that can not be a directory of the cache.
+    BadAccount(String),
+    /// The server answered, but not with an input.
+    Status(u16, String),
+    /// The server could not be reached.
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingToken(var) => write!(f, "{var} is not set"),
+            FetchError::BadAccount(name) => write!(f, "account name {name:?} can not hold '/', '\\' or '..'"),
+            FetchError::Status(code, body) => write!(f, "server returned {code}: {}", body.trim()),
+            FetchError::Transport(e) => write!(f, "request failed: {e}"),
+            FetchError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> Self {
+        FetchError::Io(e)
+    }
+}
+
+/// A source of puzzle inputs.
+pub trait Fetcher {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError>;
+
+    /// Identifies whose inputs these are, since every account gets
+    /// different ones.
+    fn account(&self) -> String;
+}
+
+/// Talks to adventofcode.com, or anything that serves the same paths, as
+/// the account owning the session token.
+pub struct HttpClient {
+    base_url: String,
+    token: String,
+    account: Option<String>,
+}
+
+impl HttpClient {
+    pub fn new(token: &str) -> Self {
+        HttpClient { base_url: BASE_URL.to_string(), token: token.trim().to_string(), account: None }
+    }
+
+    /// Uses the session token from [`SESSION_VAR`].
+    pub fn from_env() -> Result<Self, FetchError> {
+        HttpClient::from_env_for(None)
+    }
+
+    /// Uses the session token for a named account from `AOC_SESSION_<NAME>`,
+    /// or from [`SESSION_VAR`] when no account is named.
+    pub fn from_env_for(account: Option<&str>) -> Result<Self, FetchError> {
+        let var = match account {
+            Some(name) => format!("{SESSION_VAR}_{}", name.to_uppercase().replace(|c: char| !c.is_ascii_alphanumeric(), "_")),
+            None => SESSION_VAR.to_string(),
+        };
+
+        let client = HttpClient::for_account(account, &std::env::var(&var).unwrap_or_default())?;
+        match client.token.is_empty() {
+            true => Err(FetchError::MissingToken(var)),
+            false => Ok(client),
+        }
+    }
+
+    /// Uses `token` for a named account. The name is a directory of the
+    /// cache, so it may not hold path separators or `..`.
+    pub fn for_account(account: Option<&str>, token: &str) -> Result<Self, FetchError> {
+        match account {
+            Some(name) if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") => {
+                Err(FetchError::BadAccount(name.to_string()))
+            }
+            _ => Ok(HttpClient { account: account.map(|a| a.to_string()), ..HttpClient::new(token) }),
+        }
+    }
+
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub(crate) fn request(&self, method: &str, path: &str) -> ureq::Request {
+        ureq::request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.token))
+            .set("User-Agent", "github.com/tmcg/AdventOfCode2023")
+    }
+}
+
+/// The body of a successful response.
+pub(crate) fn body(response: Result<ureq::Response, ureq::Error>) -> Result<String, FetchError> {
+    match response {
+        Ok(r) => Ok(r.into_string()?),
+        Err(ureq::Error::Status(code, r)) => Err(FetchError::Status(code, r.into_string().unwrap_or_default())),
+        Err(e) => Err(FetchError::Transport(e.to_string())),
+    }
+}
+
+impl Fetcher for HttpClient {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        body(self.request("GET", &format!("/{year}/day/{day}/input")).call())
+    }
+
+    fn account(&self) -> String {
+        match &self.account {
+            Some(name) => name.clone(),
+            None => format!("{:016x}", fnv1a(self.token.as_bytes())),
+        }
+    }
+}
+
+// Stable across builds, unlike the std hasher, so cache paths survive
+// a toolchain update. Keeps the token itself out of the path.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |h, &b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Keeps every input another fetcher returns under
+/// `dir/<account>/<year>/day-XX.txt`, and serves repeats from there.
+pub struct Cache<F> {
+    dir: PathBuf,
+    inner: F,
+}
+
+impl<F: Fetcher> Cache<F> {
+    pub fn new(dir: impl Into<PathBuf>, inner: F) -> Self {
+        Cache { dir: dir.into(), inner }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir
+            .join(self.inner.account())
+            .join(year.to_string())
+            .join(format!("day-{day:02}.txt"))
+    }
+}
+
+impl<F: Fetcher> Fetcher for Cache<F> {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let input = self.inner.fetch(year, day)?;
+        write_atomic(&path, &input)?;
+        Ok(input)
+    }
+
+    fn account(&self) -> String {
+        self.inner.account()
+    }
+}
+
+// Write beside the target and rename, so an interrupted write never
+// leaves a truncated input in the cache.
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("part");
+    fs::write(&tmp, contents)?;
+    fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    // Serves `/{year}/day/{day}/input` to requests carrying `session=secret`,
+    // counting every request it answers.
+    fn mock_server() -> (String, Arc<AtomicUsize>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let counter = hits.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let authorized = request.headers().iter()
+                    .any(|h| h.field.equiv("Cookie") && h.value.as_str() == "session=secret");
+                let parts = request.url().split('/').collect::<Vec<_>>();
+
+                let response = match (authorized, &parts[..]) {
+                    (false, _) => tiny_http::Response::from_string("Puzzle inputs differ by user.").with_status_code(400),
+                    (true, ["", year, "day", day, "input"]) => tiny_http::Response::from_string(format!("input {year} {day}\n")),
+                    _ => tiny_http::Response::from_string("404 Not Found").with_status_code(404),
+                };
+                let _ = request.respond(response);
+            }
+        });
+
+        (url, hits)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn http_fetch() {
+        let (url, _) = mock_server();
+
+        let good = HttpClient::new("secret").with_base_url(&url);
+        assert_eq!(good.fetch(2023, 5).unwrap(), "input 2023 5\n");
+
+        let bad = HttpClient::new("wrong").with_base_url(&url);
+        assert!(matches!(bad.fetch(2023, 5), Err(FetchError::Status(400, _))));
+    }
+
+    #[test]
+    fn named_account() {
+        let named = HttpClient::for_account(Some("test-work"), "secret").unwrap();
+        assert_eq!(named.account(), "test-work");
+        assert_ne!(HttpClient::new("secret").account(), "test-work");
+
+        let missing = HttpClient::from_env_for(Some("test-nobody"));
+        assert!(matches!(missing, Err(FetchError::MissingToken(v)) if v == "AOC_SESSION_TEST_NOBODY"));
+
+        for bad in ["../work", "a/b", "a\\b", "..", ""] {
+            let client = HttpClient::for_account(Some(bad), "secret");
+            assert!(matches!(client, Err(FetchError::BadAccount(n)) if n == bad), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn cache_fetches_once() {
+        let (url, hits) = mock_server();
+        let dir = temp_dir("once");
+        let cache = Cache::new(&dir, HttpClient::new("secret").with_base_url(&url));
+
+        assert_eq!(cache.fetch(2023, 7).unwrap(), "input 2023 7\n");
+        assert_eq!(cache.fetch(2023, 7).unwrap(), "input 2023 7\n");
+        assert_eq!(cache.fetch(2022, 7).unwrap(), "input 2022 7\n");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        assert!(cache.path(2023, 7).ends_with("2023/day-07.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cache_keyed_by_account() {
+        let (url, hits) = mock_server();
+        let dir = temp_dir("account");
+        let mine = Cache::new(&dir, HttpClient::new("secret").with_base_url(&url));
+        let theirs = Cache::new(&dir, HttpClient::new("other").with_base_url(&url));
+
+        assert_ne!(mine.path(2023, 1), theirs.path(2023, 1));
+        assert!(!mine.path(2023, 1).to_string_lossy().contains("secret"));
+
+        mine.fetch(2023, 1).unwrap();
+        assert!(theirs.fetch(2023, 1).is_err());
+        assert!(!theirs.path(2023, 1).exists());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+//! Reading puzzle inputs, which are committed encrypted since they may
+//! not be published. A plaintext input, such as a freshly fetched one,
+//! is read as is; otherwise the `.enc` file beside it is decrypted with
+//! the key in [`KEY_VAR`].
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+
+/// Environment variable holding the passphrase inputs are encrypted with.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum InputError {
+    /// Neither the input nor an encrypted copy of it exists.
+    Missing(PathBuf),
+    /// The input is encrypted, but [`KEY_VAR`] is not set.
+    MissingKey,
+    /// Not an encrypted input, or encrypted with a different key.
+    Undecryptable,
+    Io(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(f, "{} does not exist", path.display()),
+            InputError::MissingKey => write!(f, "the input is encrypted and {KEY_VAR} is not set"),
+            InputError::Undecryptable => write!(f, "unable to decrypt the input, check {KEY_VAR}"),
+            InputError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(e: io::Error) -> Self {
+        InputError::Io(e)
+    }
+}
+
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Key(Sha256::new()
+            .chain_update(b"aoc-input-key\0")
+            .chain_update(passphrase.trim().as_bytes())
+            .finalize()
+            .into())
+    }
+
+    /// The key in [`KEY_VAR`], if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(KEY_VAR).ok()
+            .filter(|k| !k.trim().is_empty())
+            .map(|k| Key::from_passphrase(&k))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+/// Encrypts an input. The nonce is derived from the key and the input, so
+/// encrypting the same input again gives the same bytes and leaves
+/// nothing new to commit.
+pub fn encrypt(key: &Key, input: &str) -> Vec<u8> {
+    let digest = Sha256::new()
+        .chain_update(key.0)
+        .chain_update(input.as_bytes())
+        .finalize();
+    let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+
+    let sealed = key.cipher().encrypt(nonce, input.as_bytes()).expect("input too large to encrypt");
+    [MAGIC, nonce.as_slice(), &sealed].concat()
+}
+
+pub fn decrypt(key: &Key, bytes: &[u8]) -> Result<String, InputError> {
+    let rest = bytes.strip_prefix(MAGIC).ok_or(InputError::Undecryptable)?;
+    if rest.len() < NONCE_LEN {
+        return Err(InputError::Undecryptable);
+    }
+
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let plain = key.cipher()
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| InputError::Undecryptable)?;
+    String::from_utf8(plain).map_err(|_| InputError::Undecryptable)
+}
+
+/// Where the encrypted copy of `path` is kept.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Reads `path`, or decrypts its encrypted copy when it does not exist.
+pub fn read(path: &Path) -> Result<String, InputError> {
+    read_with(path, Key::from_env().as_ref())
+}
+
+fn read_with(path: &Path, key: Option<&Key>) -> Result<String, InputError> {
+    if path.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let enc = encrypted_path(path);
+    if !enc.exists() {
+        return Err(InputError::Missing(path.to_path_buf()));
+    }
+    decrypt(key.ok_or(InputError::MissingKey)?, &fs::read(enc)?)
+}
+
+/// The `input1.txt` in a day's crate directory.
+pub fn load(dir: impl AsRef<Path>) -> Result<String, InputError> {
+    read(&dir.as_ref().join("input1.txt"))
+}
+
+/// Whether a day's input can not be read, which tests take as a reason
+/// to skip. Says why on stderr.
+pub fn unavailable(dir: impl AsRef<Path>) -> bool {
+    match load(dir) {
+        Ok(_) => false,
+        Err(e) => {
+            eprintln!("skipped: {e}");
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn roundtrip() {
+        let key = Key::from_passphrase("hunter2");
+        let sealed = encrypt(&key, "0 3 6 9 12 15\n");
+
+        assert!(sealed.starts_with(MAGIC));
+        assert!(!sealed.windows(4).any(|w| w == b"0 3 "));
+        assert_eq!(decrypt(&key, &sealed).unwrap(), "0 3 6 9 12 15\n");
+        assert_eq!(encrypt(&key, "0 3 6 9 12 15\n"), sealed);
+        assert_ne!(encrypt(&key, "0 3 6 9 12 16\n"), sealed);
+    }
+
+    #[test]
+    fn wrong_key() {
+        let sealed = encrypt(&Key::from_passphrase("hunter2"), "secret");
+
+        assert!(matches!(decrypt(&Key::from_passphrase("hunter3"), &sealed), Err(InputError::Undecryptable)));
+        assert!(matches!(decrypt(&Key::from_passphrase("hunter2"), b"secret"), Err(InputError::Undecryptable)));
+        assert!(matches!(decrypt(&Key::from_passphrase("hunter2"), MAGIC), Err(InputError::Undecryptable)));
+    }
+
+    #[test]
+    fn read_plain_or_encrypted() {
+        let dir = temp_dir("read");
+        let key = Key::from_passphrase("hunter2");
+        let path = dir.join("input1.txt");
+
+        assert!(matches!(read_with(&path, Some(&key)), Err(InputError::Missing(_))));
+
+        fs::write(encrypted_path(&path), encrypt(&key, "from enc")).unwrap();
+        assert_eq!(read_with(&path, Some(&key)).unwrap(), "from enc");
+        assert!(matches!(read_with(&path, None), Err(InputError::MissingKey)));
+
+        fs::write(&path, "from plain").unwrap();
+        assert_eq!(read_with(&path, None).unwrap(), "from plain");
+
+        assert_eq!(encrypted_path(&path), dir.join("input1.txt.enc"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,20 @@
+// Lets `#[derive(AocParse)]`, which names `::shared`, be used in here too
+extern crate self as shared;
 
-pub fn input_as_lines(s: &str) -> Vec<String> {
-    s.split("\r\n").map(|x| x.to_owned()).collect::<Vec<_>>()
+pub mod alloc;
+pub mod cancel;
+#[cfg(feature = "http")]
+pub mod fetch;
+pub mod input;
+pub mod parse;
+pub mod plugin;
+pub mod stream;
+#[cfg(feature = "http")]
+pub mod submit;
+
+/// The lines of `s`, borrowed from it, without their line endings.
+pub fn input_as_lines(s: &str) -> Vec<&str> {
+    s.lines().collect()
 }
 
 pub fn input_as_ints(s: &str) -> Vec<i64> {
@@ -21,6 +35,15 @@ pub fn lcm(nums: &[u64]) -> u64 {
     a * b / gcd(a, b)
 }
 
+/// Like `lcm`, but `None` if it does not fit in a u64. The lcm of no
+/// numbers is 1.
+pub fn checked_lcm(nums: &[u64]) -> Option<u64> {
+    nums.iter().try_fold(1, |a, &b| match gcd(a, b) {
+        0 => Some(0),
+        g => (a / g).checked_mul(b),
+    })
+}
+
 pub fn gcd(a: u64, b: u64) -> u64 {
     if b == 0 {
         return a;
@@ -0,0 +1,315 @@
+//! Parsers for puzzle lines, derived from a pattern of literal text and
+//! `{field}`s:
+//!
+//! ```
+//! use shared::parse::AocParse;
+//!
+//! #[derive(AocParse)]
+//! #[aoc("Card {id}: {winners} | {numbers}")]
+//! struct Card { id: u32, winners: Vec<u32>, numbers: Vec<u32> }
+//!
+//! let card: Card = "Card   3:  1 21 | 69 82".parse().unwrap();
+//! assert_eq!(card.winners, vec![1, 21]);
+//! ```
+//!
+//! Whitespace in the pattern matches any run of whitespace, or none, and
+//! whitespace around the line is ignored. A field takes the text up to the
+//! literal after it, and its type says how to read that text; see
+//! [`Field`]. Errors point at where the line went wrong.
+//!
+//! A struct with a lifetime can have `&str` fields, which borrow from the
+//! line instead of copying it. It is parsed with `TryFrom<&str>` rather
+//! than `FromStr`:
+//!
+//! ```
+//! use shared::parse::AocParse;
+//!
+//! #[derive(AocParse)]
+//! #[aoc("{name} = ({left}, {right})")]
+//! struct Node<'a> { name: &'a str, left: &'a str, right: &'a str }
+//!
+//! let node = Node::try_from("AAA = (BBB, CCC)").unwrap();
+//! assert_eq!(node.left, "BBB");
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+pub use parse_derive::AocParse;
+
+/// Why a line did not match, and where.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    line: String,
+    span: Range<usize>,
+    message: String,
+}
+
+impl ParseError {
+    /// The bytes of the line the error is about.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let col = self.line[..self.span.start].chars().count();
+        let width = self.line[self.span.clone()].chars().count().max(1);
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "  {}", self.line)?;
+        write!(f, "  {}{}", " ".repeat(col), "^".repeat(width))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a field's text could not be read, with the span relative to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(span: Range<usize>, message: impl Into<String>) -> Self {
+        FieldError { span, message: message.into() }
+    }
+
+    fn shift(self, by: usize) -> Self {
+        FieldError { span: self.span.start + by..self.span.end + by, ..self }
+    }
+}
+
+impl From<ParseError> for FieldError {
+    fn from(e: ParseError) -> Self {
+        FieldError { span: e.span, message: e.message }
+    }
+}
+
+/// A type a `{field}` can be, borrowing from text that lives for `'a`.
+/// Numbers, `char`, `String` and `&str` read the text as is; a `Vec`
+/// reads a list separated by whitespace or commas; and a struct deriving
+/// [`AocParse`] reads its own pattern.
+pub trait Field<'a>: Sized {
+    /// Reads `text`, which has no whitespace around it.
+    fn parse_field(text: &'a str) -> Result<Self, FieldError>;
+}
+
+macro_rules! number_fields {
+    ($($t:ty),*) => {$(
+        impl Field<'_> for $t {
+            fn parse_field(text: &str) -> Result<Self, FieldError> {
+                text.parse().map_err(|_| FieldError::new(0..text.len(), match text {
+                    "" => format!("expected {}, found nothing", stringify!($t)),
+                    _ => format!("expected {}, found {text:?}", stringify!($t)),
+                }))
+            }
+        }
+    )*};
+}
+
+number_fields!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Field<'_> for String {
+    fn parse_field(text: &str) -> Result<Self, FieldError> {
+        Ok(text.to_string())
+    }
+}
+
+impl<'a> Field<'a> for &'a str {
+    fn parse_field(text: &'a str) -> Result<Self, FieldError> {
+        Ok(text)
+    }
+}
+
+impl Field<'_> for char {
+    fn parse_field(text: &str) -> Result<Self, FieldError> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FieldError::new(0..text.len(), format!("expected one character, found {text:?}"))),
+        }
+    }
+}
+
+impl<'a, T: Field<'a>> Field<'a> for Vec<T> {
+    fn parse_field(text: &'a str) -> Result<Self, FieldError> {
+        let sep = |c: char| c.is_whitespace() || c == ',';
+        let mut items = vec![];
+        let mut rest = text.trim_start_matches(sep);
+
+        while !rest.is_empty() {
+            let start = text.len() - rest.len();
+            let end = rest.find(sep).unwrap_or(rest.len());
+            items.push(T::parse_field(&rest[..end]).map_err(|e| e.shift(start))?);
+            rest = rest[end..].trim_start_matches(sep);
+        }
+        Ok(items)
+    }
+}
+
+/// Walks a line for a derived parser.
+#[doc(hidden)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: &'a str) -> Self {
+        let mut cursor = Cursor { line, pos: 0 };
+        cursor.skip_space();
+        cursor
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn skip_space(&mut self) {
+        self.pos = self.line.len() - self.rest().trim_start().len();
+    }
+
+    fn error(&self, span: Range<usize>, message: String) -> ParseError {
+        ParseError { line: self.line.to_string(), span, message }
+    }
+
+    // The word from `at` on, for saying what was found instead
+    fn found(&self, at: usize) -> (Range<usize>, String) {
+        let rest = self.line[at..].trim_start();
+        let at = self.line.len() - rest.len();
+        match rest.split_whitespace().next() {
+            Some(word) => (at..at + word.len(), format!("{word:?}")),
+            None => (at..at, "the end of the line".to_string()),
+        }
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        let start = self.pos;
+        for c in literal.chars() {
+            if c.is_whitespace() {
+                self.skip_space();
+            } else if self.rest().starts_with(c) {
+                self.pos += c.len_utf8();
+            } else {
+                let (span, found) = self.found(start);
+                return Err(self.error(span, format!("expected {:?}, found {found}", literal.trim())));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the field `name`, up to where the literal `until` starts, or
+    /// to the end of the line.
+    pub fn field<T: Field<'a>>(&mut self, name: &str, until: Option<&str>) -> Result<T, ParseError> {
+        self.skip_space();
+        let rest = self.rest();
+        let len = match until.map(|u| u.split_whitespace().next()) {
+            None => rest.len(),
+            // Only whitespace follows, so the field is one word
+            Some(None) => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            Some(Some(word)) => rest.find(word).unwrap_or(rest.len()),
+        };
+
+        let start = self.pos;
+        self.pos += len;
+        T::parse_field(rest[..len].trim_end())
+            .map_err(|e| self.error(start + e.span.start..start + e.span.end, format!("{name}: {}", e.message)))
+    }
+
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_space();
+        match self.rest().is_empty() {
+            true => Ok(()),
+            false => {
+                let (span, found) = (self.pos..self.line.len(), self.rest());
+                Err(self.error(span, format!("expected the end of the line, found {found:?}")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc("Card {id}: {winners} | {numbers}")]
+    struct Card {
+        id: u32,
+        winners: Vec<u32>,
+        numbers: Vec<u32>,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc("{name} = ({left}, {right})")]
+    struct Node {
+        name: String,
+        left: String,
+        right: String,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc("{name} = ({exits})")]
+    struct Borrowed<'a> {
+        name: &'a str,
+        exits: Vec<&'a str>,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc("{label} {card}")]
+    struct Labelled {
+        label: char,
+        card: Card,
+    }
+
+    #[test]
+    fn parse_lines() {
+        let card: Card = "  Card   3:  1 21 53|69 82,63  ".parse().unwrap();
+        assert_eq!(card, Card { id: 3, winners: vec![1, 21, 53], numbers: vec![69, 82, 63] });
+
+        let card: Card = "Card 4:  | ".parse().unwrap();
+        assert_eq!(card, Card { id: 4, winners: vec![], numbers: vec![] });
+
+        let node: Node = "AAA = (BBB, CCC)".parse().unwrap();
+        assert_eq!(node, Node { name: "AAA".to_string(), left: "BBB".to_string(), right: "CCC".to_string() });
+
+        let labelled: Labelled = "x Card 1: 2 | 3".parse().unwrap();
+        assert_eq!(labelled.label, 'x');
+        assert_eq!(labelled.card.numbers, vec![3]);
+
+        let line = "AAA = (BBB, CCC)".to_string();
+        let borrowed = Borrowed::try_from(line.as_str()).unwrap();
+        assert_eq!(borrowed, Borrowed { name: "AAA", exits: vec!["BBB", "CCC"] });
+        assert!(std::ptr::eq(borrowed.name.as_ptr(), line.as_ptr()));
+        assert_eq!(Borrowed::try_from("AAA (BBB)").unwrap_err().span(), 9..9);
+    }
+
+    #[test]
+    fn point_at_errors() {
+        let e = "Card 3: 1 2x 3 | 4".parse::<Card>().unwrap_err();
+        assert_eq!(e.span(), 10..12);
+        assert_eq!(e.to_string(), "winners: expected u32, found \"2x\"\n  Card 3: 1 2x 3 | 4\n            ^^");
+
+        let e = "Card 3: 1 2 3".parse::<Card>().unwrap_err();
+        assert_eq!((e.span(), e.message()), (13..13, "expected \"|\", found the end of the line"));
+
+        let e = "Cart 3: 1 | 2".parse::<Card>().unwrap_err();
+        assert_eq!((e.span(), e.message()), (0..4, "expected \"Card\", found \"Cart\""));
+
+        let e = "AAA = (BBB, CCC) extra".parse::<Node>().unwrap_err();
+        assert_eq!((e.span(), e.message()), (17..22, "expected the end of the line, found \"extra\""));
+
+        let e = "x Card : 2 | 3".parse::<Labelled>().unwrap_err();
+        assert_eq!((e.span(), e.message()), (7..7, "card: id: expected u32, found nothing"));
+
+        let e = "xy Card 1: 2 | 3".parse::<Labelled>().unwrap_err();
+        assert_eq!(e.span(), 0..2);
+    }
+}
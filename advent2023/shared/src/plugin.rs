@@ -0,0 +1,188 @@
+//! The C ABI between the runner and solver plugins: `cdylib` crates the
+//! runner loads at start up, which add variants to days or shadow the
+//! ones already there. A plugin exports these functions, most easily
+//! through [`aoc_plugin!`](crate::aoc_plugin):
+//!
+//! - `aoc_plugin_abi() -> u32` gives the [`ABI_VERSION`] it was built for.
+//! - `aoc_plugin_parts(len: *mut usize) -> *const PluginPart` lists the
+//!   parts it solves.
+//! - `aoc_plugin_solve(year, day, part, variant, input, len) -> *mut c_char`
+//!   solves one of them, giving a NUL terminated answer, or null if it
+//!   failed.
+//! - `aoc_plugin_free(answer: *mut c_char)` frees an answer.
+//!
+//! ```ignore
+//! fn solve_fast(input: &str) -> String { ... }
+//!
+//! shared::aoc_plugin! {
+//!     (2023, 5, 2, "fast") => solve_fast,
+//!     (2023, 9, 1, "default") => day_09::solve_part1,
+//! }
+//! ```
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Bumped whenever the functions above change.
+pub const ABI_VERSION: u32 = 1;
+
+/// One part a plugin solves. A `variant` of `"default"` shadows the day's
+/// own solver.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PluginPart {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// NUL terminated, and alive as long as the plugin is loaded.
+    pub variant: *const c_char,
+}
+
+// The variant names are static strings, only ever read
+unsafe impl Sync for PluginPart {}
+
+pub type AbiFn = unsafe extern "C" fn() -> u32;
+pub type PartsFn = unsafe extern "C" fn(len: *mut usize) -> *const PluginPart;
+pub type SolveFn = unsafe extern "C" fn(
+    year: u32,
+    day: u32,
+    part: u32,
+    variant: *const c_char,
+    input: *const u8,
+    len: usize,
+) -> *mut c_char;
+pub type FreeFn = unsafe extern "C" fn(answer: *mut c_char);
+
+/// A plugin's solvers, by year, day, part and variant.
+pub type Table = [(u32, u32, u32, &'static str, fn(&str) -> String)];
+
+/// Implements `aoc_plugin_solve` for [`aoc_plugin!`](crate::aoc_plugin).
+/// A solver that panics, or input that is not UTF-8, gives null.
+///
+/// # Safety
+///
+/// `variant` must be NUL terminated, and `input` must point to `len`
+/// readable bytes.
+#[doc(hidden)]
+pub unsafe fn solve(
+    table: &Table,
+    (year, day, part): (u32, u32, u32),
+    variant: *const c_char,
+    input: *const u8,
+    len: usize,
+) -> *mut c_char {
+    let variant = CStr::from_ptr(variant).to_str().unwrap_or_default();
+    let Some(&(.., solve)) = table.iter().find(|t| (t.0, t.1, t.2, t.3) == (year, day, part, variant)) else {
+        return std::ptr::null_mut();
+    };
+    let Ok(input) = std::str::from_utf8(std::slice::from_raw_parts(input, len)) else {
+        return std::ptr::null_mut();
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(input))).ok().and_then(|a| CString::new(a).ok()) {
+        Some(answer) => answer.into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Implements `aoc_plugin_free` for [`aoc_plugin!`](crate::aoc_plugin).
+///
+/// # Safety
+///
+/// `answer` must have come from [`solve`], and not been freed already.
+#[doc(hidden)]
+pub unsafe fn free(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
+
+/// Exports the plugin functions for a list of
+/// `(year, day, part, "variant") => solver` entries.
+#[macro_export]
+macro_rules! aoc_plugin {
+    ($(($year:expr, $day:expr, $part:expr, $variant:literal) => $solve:path),* $(,)?) => {
+        static __AOC_PLUGIN_TABLE: &$crate::plugin::Table = &[
+            $(($year, $day, $part, $variant, $solve as fn(&str) -> String)),*
+        ];
+        static __AOC_PLUGIN_PARTS: &[$crate::plugin::PluginPart] = &[
+            $($crate::plugin::PluginPart {
+                year: $year,
+                day: $day,
+                part: $part,
+                variant: concat!($variant, "\0").as_ptr().cast(),
+            }),*
+        ];
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        /// # Safety
+        ///
+        /// `len` must be writable.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_parts(len: *mut usize) -> *const $crate::plugin::PluginPart {
+            *len = __AOC_PLUGIN_PARTS.len();
+            __AOC_PLUGIN_PARTS.as_ptr()
+        }
+
+        /// # Safety
+        ///
+        /// See `shared::plugin::solve`.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_solve(
+            year: u32,
+            day: u32,
+            part: u32,
+            variant: *const ::std::ffi::c_char,
+            input: *const u8,
+            len: usize,
+        ) -> *mut ::std::ffi::c_char {
+            $crate::plugin::solve(__AOC_PLUGIN_TABLE, (year, day, part), variant, input, len)
+        }
+
+        /// # Safety
+        ///
+        /// See `shared::plugin::free`.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_free(answer: *mut ::std::ffi::c_char) {
+            $crate::plugin::free(answer)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shout(input: &str) -> String {
+        input.to_uppercase()
+    }
+
+    fn broken(_: &str) -> String {
+        panic!("broken")
+    }
+
+    static TABLE: &Table = &[(2023, 1, 1, "shout", shout), (2023, 1, 2, "default", broken)];
+
+    // What `aoc_plugin_solve` would give, as text
+    fn call(day: u32, part: u32, variant: &CStr, input: &[u8]) -> Option<String> {
+        unsafe {
+            let answer = solve(TABLE, (2023, day, part), variant.as_ptr(), input.as_ptr(), input.len());
+            let text = (!answer.is_null()).then(|| CStr::from_ptr(answer).to_string_lossy().into_owned());
+            free(answer);
+            text
+        }
+    }
+
+    #[test]
+    fn solve_through_table() {
+        assert_eq!(call(1, 1, c"shout", b"abc").as_deref(), Some("ABC"));
+        assert_eq!(call(1, 1, c"default", b"abc"), None);
+        assert_eq!(call(2, 1, c"shout", b"abc"), None);
+        assert_eq!(call(1, 1, c"shout", b"\xff"), None);
+        assert_eq!(call(1, 2, c"default", b"abc"), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! Solving from a reader a line at a time, so that an input far bigger than
+//! memory can be solved without loading it.
+
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+/// Folds each line of `reader`, without its line ending, into `init`. One
+/// buffer is reused for every line.
+pub fn fold_lines<R: BufRead, B>(mut reader: R, init: B, mut f: impl FnMut(B, &str) -> B) -> io::Result<B> {
+    let mut acc = init;
+    let mut buf = String::new();
+
+    while let Some(line) = next_line(&mut reader, &mut buf)? {
+        acc = f(acc, line);
+    }
+    Ok(acc)
+}
+
+/// Adds up `f` of each line.
+pub fn sum_lines<R: BufRead, T>(reader: R, mut f: impl FnMut(&str) -> T) -> io::Result<T>
+where
+    T: std::ops::Add<Output = T> + Default,
+{
+    fold_lines(reader, T::default(), |total, line| total + f(line))
+}
+
+/// Parses each line that is not blank as a `T` and folds it into `init`.
+/// A line that does not parse stops the fold with an `InvalidData` error
+/// saying which line it was.
+pub fn fold_parsed<R: BufRead, T, B>(reader: R, init: B, mut f: impl FnMut(B, T) -> B) -> io::Result<B>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    try_fold_parsed(reader, init, |acc, item| Ok::<_, Infallible>(f(acc, item)))
+}
+
+/// Like `fold_parsed`, but `f` can fail too, as when the answer gets too
+/// big for its type. Its error stops the fold the same way.
+pub fn try_fold_parsed<R: BufRead, T, B, E>(mut reader: R, init: B, mut f: impl FnMut(B, T) -> Result<B, E>) -> io::Result<B>
+where
+    T: FromStr,
+    T::Err: Display,
+    E: Display,
+{
+    let invalid = |n, e: &dyn Display| io::Error::new(io::ErrorKind::InvalidData, format!("line {n}: {e}"));
+    let mut acc = init;
+    let mut buf = String::new();
+
+    for n in 1.. {
+        let Some(line) = next_line(&mut reader, &mut buf)? else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = line.parse().map_err(|e| invalid(n, &e))?;
+        acc = f(acc, item).map_err(|e| invalid(n, &e))?;
+    }
+    Ok(acc)
+}
+
+// Reads the next line into `buf`, and gives it back without its ending
+fn next_line<'a>(reader: &mut impl BufRead, buf: &'a mut String) -> io::Result<Option<&'a str>> {
+    buf.clear();
+    if reader.read_line(buf)? == 0 {
+        return Ok(None);
+    }
+    let line = buf.strip_suffix('\n').unwrap_or(buf);
+    Ok(Some(line.strip_suffix('\r').unwrap_or(line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    // This many lines of "1 2 3", made up as they are read
+    struct Repeat(usize);
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let line = b"1 2 3\n";
+            let n = buf.len().min(line.len()).min(self.0 * line.len());
+            buf[..n].copy_from_slice(&line[..n]);
+            self.0 -= (n == line.len()) as usize;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn fold_line_endings() {
+        let lines = fold_lines("a\r\nb\n\nc".as_bytes(), vec![], |mut v, l| { v.push(l.to_string()); v }).unwrap();
+        assert_eq!(lines, vec!["a", "b", "", "c"]);
+        assert_eq!(sum_lines("".as_bytes(), |l| l.len()).unwrap(), 0);
+    }
+
+    #[test]
+    fn sum_long_input() {
+        let reader = io::BufReader::new(Repeat(1_000_000));
+        let total = sum_lines(reader, |l| l.split(' ').map(|x| x.parse::<u64>().unwrap()).sum::<u64>()).unwrap();
+        assert_eq!(total, 6_000_000);
+    }
+
+    #[test]
+    fn stop_at_bad_line() {
+        let sum = |input: &str| fold_parsed(input.as_bytes(), 0, |total, x: i64| total + x);
+
+        assert_eq!(sum("1\n2\n\n3\n").unwrap(), 6);
+        let e = sum("1\nx\n3").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "line 2: invalid digit found in string");
+
+        let e = try_fold_parsed("100\n200\n3".as_bytes(), 0u8, |total, x: u8| total.checked_add(x).ok_or("too big")).unwrap_err();
+        assert_eq!(e.to_string(), "line 2: too big");
+    }
+}
@@ -0,0 +1,360 @@
+//! Submitting answers, and remembering every guess so known-wrong answers
+//! are never sent twice.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::fetch::{body, FetchError, HttpClient};
+
+/// How the server judged an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted during a lockout; the answer was not checked.
+    TooSoon,
+    /// The part was already solved; the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too-soon",
+            Verdict::AlreadySolved => "already-solved",
+        }
+    }
+
+    fn from_name(s: &str) -> Option<Verdict> {
+        [Verdict::Correct, Verdict::TooHigh, Verdict::TooLow, Verdict::Wrong, Verdict::TooSoon, Verdict::AlreadySolved]
+            .into_iter()
+            .find(|v| v.name() == s)
+    }
+
+    /// Whether the answer itself was judged wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A parsed answer page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// Seconds before the next answer will be accepted.
+    pub wait: u64,
+}
+
+/// Reads the verdict out of an answer page, or `None` if the page is not
+/// one we recognize.
+pub fn parse_response(page: &str) -> Option<Response> {
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if page.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        return None;
+    };
+
+    Some(Response { verdict, wait: parse_wait(page) })
+}
+
+// "You have 1m 5s left to wait." after a lockout, or "please wait one
+// minute before trying again" / "wait 5 minutes" after a wrong answer.
+fn parse_wait(page: &str) -> u64 {
+    if let Some(i) = page.find(" left to wait") {
+        let start = page[..i].rfind("You have ").map_or(0, |j| j + 9);
+        return page[start..i].split_whitespace()
+            .filter_map(|t| {
+                let (n, unit) = t.split_at(t.find(|c: char| !c.is_ascii_digit())?);
+                let n = n.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(n * 3600),
+                    "m" => Some(n * 60),
+                    "s" => Some(n),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    let Some(i) = page.find("wait ") else { return 0 };
+    let mut words = page[i + 5..].split_whitespace();
+    let n = match words.next() {
+        Some("one") => 1,
+        Some(w) => w.parse().unwrap_or(0),
+        None => 0,
+    };
+    match words.next() {
+        Some(u) if u.starts_with("minute") => n * 60,
+        Some(u) if u.starts_with("second") => n,
+        _ => 0,
+    }
+}
+
+/// Somewhere to send answers.
+pub trait Submitter {
+    fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> Result<Response, FetchError>;
+}
+
+impl Submitter for HttpClient {
+    fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> Result<Response, FetchError> {
+        let level = part.to_string();
+        let page = body(self.request("POST", &format!("/{year}/day/{day}/answer"))
+            .send_form(&[("level", &level), ("answer", answer)]))?;
+
+        parse_response(&page).ok_or_else(|| FetchError::Status(200, "unrecognized answer page".to_string()))
+    }
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix time of the submission.
+    pub at: u64,
+    /// Seconds the server asked us to wait afterwards.
+    pub wait: u64,
+}
+
+impl Guess {
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.year, self.day, self.part, self.answer, self.verdict, self.at, self.wait)
+    }
+
+    fn parse(line: &str) -> Option<Guess> {
+        let f = line.split('\t').collect::<Vec<_>>();
+        if f.len() != 7 {
+            return None;
+        }
+
+        Some(Guess {
+            year: f[0].parse().ok()?,
+            day: f[1].parse().ok()?,
+            part: f[2].parse().ok()?,
+            answer: f[3].to_string(),
+            verdict: Verdict::from_name(f[4])?,
+            at: f[5].parse().ok()?,
+            wait: f[6].parse().ok()?,
+        })
+    }
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part is solved; holds the accepted answer.
+    Solved(String),
+    /// This exact answer was already judged wrong.
+    KnownWrong(Verdict),
+    /// At least as high as a guess that was too high.
+    AboveTooHigh(String),
+    /// At most as low as a guess that was too low.
+    BelowTooLow(String),
+    /// Seconds left before the server will take another answer.
+    LockedOut(u64),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::Solved(a) => write!(f, "already solved with {a}"),
+            Refusal::KnownWrong(v) => write!(f, "already submitted, and it was {v}"),
+            Refusal::AboveTooHigh(g) => write!(f, "{g} was already too high"),
+            Refusal::BelowTooLow(g) => write!(f, "{g} was already too low"),
+            Refusal::LockedOut(s) => write!(f, "locked out for another {s}s"),
+        }
+    }
+}
+
+/// Every answer submitted from this account, kept in a tab separated file.
+pub struct Ledger {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, which need not exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let guesses = match fs::read_to_string(&path) {
+            Ok(text) => text.lines().filter_map(Guess::parse).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+
+        Ok(Ledger { path, guesses })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Guesses made for one part, oldest first.
+    pub fn guesses(&self, year: u32, day: u32, part: u32) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| (g.year, g.day, g.part) == (year, day, part))
+    }
+
+    /// Whether `answer` is worth sending at unix time `now`.
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str, now: u64) -> Result<(), Refusal> {
+        let locked_until = self.guesses.iter().map(|g| g.at + g.wait).max().unwrap_or(0);
+        let value = answer.parse::<i128>().ok();
+
+        for g in self.guesses(year, day, part) {
+            let bound = g.answer.parse::<i128>().ok();
+            match (g.verdict, value, bound) {
+                (Verdict::Correct, _, _) => return Err(Refusal::Solved(g.answer.clone())),
+                (v, _, _) if v.is_wrong() && g.answer == answer => return Err(Refusal::KnownWrong(v)),
+                (Verdict::TooHigh, Some(a), Some(b)) if a >= b => return Err(Refusal::AboveTooHigh(g.answer.clone())),
+                (Verdict::TooLow, Some(a), Some(b)) if a <= b => return Err(Refusal::BelowTooLow(g.answer.clone())),
+                _ => {}
+            }
+        }
+
+        match locked_until > now {
+            true => Err(Refusal::LockedOut(locked_until - now)),
+            false => Ok(()),
+        }
+    }
+
+    pub fn record(&mut self, guess: Guess) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", guess.to_line())?;
+
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // Stands in for the answer endpoint. The answer to every part is 42.
+    fn stand_in_server() -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut form = String::new();
+                request.as_reader().read_to_string(&mut form).unwrap();
+                let answer = form.split('&')
+                    .find_map(|kv| kv.strip_prefix("answer="))
+                    .and_then(|a| a.parse::<i64>().ok());
+
+                let page = match answer {
+                    Some(42) => "That's the right answer!  You are one gold star closer to restoring snow operations.",
+                    Some(a) if a > 42 => "That's not the right answer; your answer is too high.  Please wait one minute before trying again.",
+                    Some(_) => "That's not the right answer; your answer is too low.  Please wait one minute before trying again.",
+                    None => "That's not the right answer.  Please wait one minute before trying again.",
+                };
+                let _ = request.respond(tiny_http::Response::from_string(format!("<main><article><p>{page}</p></article></main>")));
+            }
+        });
+
+        url
+    }
+
+    fn guess(answer: &str, verdict: Verdict, at: u64, wait: u64) -> Guess {
+        Guess { year: 2023, day: 7, part: 2, answer: answer.to_string(), verdict, at, wait }
+    }
+
+    #[test]
+    fn parse_pages() {
+        let parse = |page| parse_response(page).map(|r| (r.verdict, r.wait));
+
+        assert_eq!(parse("That's the right answer!"), Some((Verdict::Correct, 0)));
+        assert_eq!(parse("That's not the right answer; your answer is too high.  Please wait one minute before trying again."), Some((Verdict::TooHigh, 60)));
+        assert_eq!(parse("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again."), Some((Verdict::TooLow, 300)));
+        assert_eq!(parse("That's not the right answer.  If you're stuck, make sure you're using the full input data."), Some((Verdict::Wrong, 0)));
+        assert_eq!(parse("You gave an answer too recently.  You have 1m 5s left to wait."), Some((Verdict::TooSoon, 65)));
+        assert_eq!(parse("You don't seem to be solving the right level.  Did you already complete it?"), Some((Verdict::AlreadySolved, 0)));
+        assert_eq!(parse("<html>Internal error</html>"), None);
+    }
+
+    #[test]
+    fn guess_roundtrip() {
+        let g = guess("12345", Verdict::TooLow, 1_700_000_000, 60);
+        assert_eq!(Guess::parse(&g.to_line()), Some(g));
+    }
+
+    #[test]
+    fn refuse_known_wrong() {
+        let dir = std::env::temp_dir().join(format!("aoc-ledger-{}", std::process::id()));
+        let mut ledger = Ledger::load(dir.join("answers.tsv")).unwrap();
+        ledger.record(guess("100", Verdict::TooHigh, 1000, 60)).unwrap();
+        ledger.record(guess("10", Verdict::TooLow, 2000, 60)).unwrap();
+        ledger.record(guess("abc", Verdict::Wrong, 1500, 0)).unwrap();
+
+        let ledger = Ledger::load(dir.join("answers.tsv")).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+
+        let check = |answer| ledger.check(2023, 7, 2, answer, 5000);
+        assert_eq!(check("100"), Err(Refusal::KnownWrong(Verdict::TooHigh)));
+        assert_eq!(check("150"), Err(Refusal::AboveTooHigh("100".to_string())));
+        assert_eq!(check("3"), Err(Refusal::BelowTooLow("10".to_string())));
+        assert_eq!(check("abc"), Err(Refusal::KnownWrong(Verdict::Wrong)));
+        assert_eq!(check("50"), Ok(()));
+
+        assert_eq!(ledger.check(2023, 7, 2, "50", 2030), Err(Refusal::LockedOut(30)));
+        assert_eq!(ledger.check(2023, 7, 1, "150", 5000), Ok(()));
+    }
+
+    #[test]
+    fn submit_to_stand_in() {
+        let client = HttpClient::new("secret").with_base_url(&stand_in_server());
+        let mut ledger = Ledger { path: PathBuf::new(), guesses: vec![] };
+
+        // Each attempt is 30s after the last
+        let mut now = 0;
+        let mut send = |answer: &str, ledger: &mut Ledger| {
+            now += 30;
+            ledger.check(2023, 7, 2, answer, now)?;
+            let r = client.submit(2023, 7, 2, answer).unwrap();
+            ledger.guesses.push(guess(answer, r.verdict, now, r.wait));
+            Ok::<_, Refusal>(r.verdict)
+        };
+
+        assert_eq!(send("50", &mut ledger), Ok(Verdict::TooHigh));
+        assert_eq!(send("40", &mut ledger), Err(Refusal::LockedOut(30)));
+        assert_eq!(send("60", &mut ledger), Err(Refusal::AboveTooHigh("50".to_string())));
+        assert_eq!(send("40", &mut ledger), Ok(Verdict::TooLow));
+        assert_eq!(send("40", &mut ledger), Err(Refusal::KnownWrong(Verdict::TooLow)));
+        assert_eq!(send("42", &mut ledger), Ok(Verdict::Correct));
+        assert_eq!(send("43", &mut ledger), Err(Refusal::Solved("42".to_string())));
+    }
+}